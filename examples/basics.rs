@@ -88,6 +88,7 @@ async fn main() {
                     other,
                     TagType::MovingSensor,
                     _any,
+                    _contact,
                 ) = event
                 {
                     to_remove.push(*other);
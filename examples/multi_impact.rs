@@ -3,6 +3,7 @@ use macroquad::*;
 use resphys::{Collider, ColliderState, AABB};
 
 // A test if collision gets resolved properly even if multiple impacts happen
+// Uses the built-in fixed timestep with interpolated rendering
 
 const FPS_INV: f32 = 1. / 60.;
 
@@ -44,12 +45,9 @@ async fn main() {
     let handle3 = bodies.insert(body3);
     colliders.insert(collider3.build(handle3), &mut bodies, &mut physics);
 
-    let mut remaining_time = 0.;
+    physics.timestep = FPS_INV;
     loop {
-        remaining_time += get_frame_time();
-        while remaining_time >= FPS_INV {
-            physics.step(FPS_INV, &mut bodies, &mut colliders);
-
+        if physics.advance(get_frame_time(), &mut bodies, &mut colliders) > 0 {
             for event in physics.events().iter() {
                 println!("{:?}", event);
             }
@@ -59,14 +57,13 @@ async fn main() {
                 .for_each(|(first, second)| {
                     println!("Collider: {:?}, Weight: {:?}", first, second)
                 });
-
-            remaining_time -= FPS_INV;
         }
 
         clear_background(Color::new(0., 1., 1., 1.));
+        let alpha = physics.alpha();
         for (_, collider) in colliders.iter() {
             let body = &bodies[collider.owner];
-            draw_collider(collider, body.position);
+            draw_collider(collider, body.interpolated_position(alpha));
         }
 
         next_frame().await
@@ -2,7 +2,7 @@ use glam::Vec2;
 use macroquad::*;
 use resphys::{Collider, ColliderState, AABB};
 
-// Crude character controller
+// Character controller moving the player with `move_and_slide`
 
 extern crate log;
 
@@ -65,15 +65,32 @@ async fn main() {
         );
     }
 
+    let controller = resphys::CharacterController::new();
+    let mut velocity = Vec2::ZERO;
+    let mut on_ground = false;
+
     let mut remaining_time = 0.;
     loop {
         remaining_time += get_frame_time();
         while remaining_time >= FPS_INV {
-            let player_body = &mut bodies[player_bhandle];
-
-            player_body.velocity += Vec2::new(0., 64. * FPS_INV);
-
-            player_body.velocity = controls(player_body.velocity);
+            velocity += Vec2::new(0., 64. * FPS_INV);
+
+            velocity = controls(velocity, on_ground);
+
+            let motion = controller.move_and_slide(
+                player_bhandle,
+                velocity * FPS_INV,
+                &mut physics,
+                &mut bodies,
+                &colliders,
+            );
+            if motion.on_ground || motion.on_ceiling {
+                velocity.y = 0.;
+            }
+            if motion.on_wall {
+                velocity.x = 0.;
+            }
+            on_ground = motion.on_ground;
 
             physics.step(FPS_INV, &mut bodies, &mut colliders);
             remaining_time -= FPS_INV;
@@ -90,7 +107,7 @@ async fn main() {
 }
 
 // 32 is tile per second
-fn controls(mut velocity: Vec2) -> Vec2 {
+fn controls(mut velocity: Vec2, on_ground: bool) -> Vec2 {
     let input: f32 = {
         if is_key_down(KeyCode::Left) {
             -1.
@@ -111,8 +128,8 @@ fn controls(mut velocity: Vec2) -> Vec2 {
 
     velocity.x = velocity.x.max(-32. * 4.).min(32. * 4.);
 
-    if is_key_pressed(KeyCode::Up) {
-        velocity = Vec2::new(0., -128.);
+    if on_ground && is_key_pressed(KeyCode::Up) {
+        velocity.y = -128.;
     }
     velocity
 }
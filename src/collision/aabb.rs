@@ -6,16 +6,46 @@ pub struct AABB {
     pub half_exts: Vec2,
 }
 
-/// Minimal structure containing only the most important information about the collision.
+/// Point of the contact together with the penetration depth at it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContactPoint {
+    pub point: Vec2,
+    pub depth: f32,
+}
+
+/// Contact manifold of the collision.  
+/// The normal points from the first collider of the interaction towards the second one.
 #[derive(Debug, Clone)]
 pub struct CollisionInfo {
     pub normal: Vec2,
+    /// Ends of the segment where the colliders meet, only the first `point_count` are valid
+    pub points: [ContactPoint; 2],
+    pub point_count: usize,
+}
+
+impl CollisionInfo {
+    pub fn contact_points(&self) -> &[ContactPoint] {
+        &self.points[..self.point_count]
+    }
 }
 
 impl From<&Contact> for CollisionInfo {
     fn from(contact: &Contact) -> Self {
+        let [start, end] = contact.segment;
+        let to_point = |point| ContactPoint {
+            point,
+            depth: contact.depth,
+        };
+        // colliders touching only with corners share a single point
+        let point_count = if start.distance_squared(end) > f32::EPSILON {
+            2
+        } else {
+            1
+        };
         Self {
             normal: contact.normal,
+            points: [to_point(start), to_point(end)],
+            point_count,
         }
     }
 }
@@ -24,15 +54,19 @@ impl From<&Contact> for CollisionInfo {
 pub struct Contact {
     pub depth: f32,
     pub normal: Vec2,
+    /// Middle of the `segment`
     pub contact_point: Vec2,
+    /// Ends of the segment where the colliders meet, halfway through the penetration
+    pub segment: [Vec2; 2],
 }
 
 impl Contact {
-    pub fn new(depth: f32, normal: Vec2, contact_point: Vec2) -> Self {
+    pub fn new(depth: f32, normal: Vec2, segment: [Vec2; 2]) -> Self {
         Self {
             depth,
             normal,
-            contact_point,
+            contact_point: segment[0].lerp(segment[1], 0.5),
+            segment,
         }
     }
 }
@@ -73,7 +107,6 @@ pub fn intersection_aabb_aabb(
     !(d0 | d1 | d2 | d3)
 }
 
-// contact points aren't precise - they are currently in the middle on the correct side
 pub fn contact_aabb_aabb(
     a_loc: Vec2,
     a_half_exts: Vec2,
@@ -88,18 +121,84 @@ pub fn contact_aabb_aabb(
         return None;
     }
 
+    // part of the boxes shared on the axis perpendicular to the normal
+    let shared_min = (a_loc - a_half_exts).max(b_loc - b_half_exts);
+    let shared_max = (a_loc + a_half_exts).min(b_loc + b_half_exts);
+
     let depth1 = overlap.x;
     let normal1 = Vec2::new(distance.x.signum(), 0.);
-    let contact_point_x = Vec2::new(a_loc.x + a_half_exts.x * normal1.x, a_loc.y);
-    let contact1 = Contact::new(depth1, normal1, contact_point_x);
+    let edge_x = a_loc.x + (a_half_exts.x - depth1 * 0.5) * normal1.x;
+    let segment_x = [
+        Vec2::new(edge_x, shared_min.y),
+        Vec2::new(edge_x, shared_max.y),
+    ];
+    let contact1 = Contact::new(depth1, normal1, segment_x);
 
     let depth2 = overlap.y;
     let normal2 = Vec2::new(0., distance.y.signum());
-    let contact_point_y = Vec2::new(a_loc.x, a_loc.y + a_half_exts.y * normal2.y);
-    let contact2 = Contact::new(depth2, normal2, contact_point_y);
+    let edge_y = a_loc.y + (a_half_exts.y - depth2 * 0.5) * normal2.y;
+    let segment_y = [
+        Vec2::new(shared_min.x, edge_y),
+        Vec2::new(shared_max.x, edge_y),
+    ];
+    let contact2 = Contact::new(depth2, normal2, segment_y);
 
     Some(ContactManifold {
         contact_x: contact1,
         contact_y: contact2,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{contact_aabb_aabb, CollisionInfo};
+    use glam::Vec2;
+
+    #[test]
+    fn resting_contact_spans_the_shared_edge() {
+        let manifold = contact_aabb_aabb(
+            Vec2::new(4., -15.),
+            Vec2::new(8., 8.),
+            Vec2::new(0., 0.),
+            Vec2::new(16., 8.),
+        )
+        .unwrap();
+        let contact = manifold.best_contact();
+        assert_eq!(contact.normal, Vec2::new(0., 1.));
+        assert_eq!(contact.depth, 1.);
+        assert_eq!(
+            contact.segment,
+            [Vec2::new(-4., -7.5), Vec2::new(12., -7.5)]
+        );
+        assert_eq!(contact.contact_point, Vec2::new(4., -7.5));
+
+        let info = CollisionInfo::from(contact);
+        assert_eq!(info.point_count, 2);
+        assert!(info.contact_points().iter().all(|point| point.depth == 1.));
+    }
+
+    #[test]
+    fn touching_corners_give_a_single_point() {
+        let manifold = contact_aabb_aabb(
+            Vec2::new(0., 0.),
+            Vec2::new(8., 8.),
+            Vec2::new(16., 16.),
+            Vec2::new(8., 8.),
+        )
+        .unwrap();
+        let info = CollisionInfo::from(manifold.best_contact());
+        assert_eq!(info.point_count, 1);
+        assert_eq!(info.contact_points()[0].point, Vec2::new(8., 8.));
+    }
+
+    #[test]
+    fn separated_boxes_dont_touch() {
+        assert!(contact_aabb_aabb(
+            Vec2::new(0., 0.),
+            Vec2::new(8., 8.),
+            Vec2::new(16.5, 0.),
+            Vec2::new(8., 8.),
+        )
+        .is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::collision::aabb::CollisionInfo;
-use crate::ColliderHandle;
+use crate::{ColliderHandle, Error};
 use fxhash::FxHashMap;
 use petgraph::graph::{NodeIndex, UnGraph};
 use petgraph::visit::EdgeRef;
@@ -49,6 +49,12 @@ impl CollisionGraph {
     pub fn get_node_index(&self, handle: ColliderHandle) -> NodeIndex<usize> {
         *self.binding.get(&handle).unwrap()
     }
+    pub fn try_get_node_index(&self, handle: ColliderHandle) -> Result<NodeIndex<usize>, Error> {
+        self.binding
+            .get(&handle)
+            .copied()
+            .ok_or(Error::GraphDesync(handle))
+    }
 
     pub fn update_edge(&mut self, handle1: ColliderHandle, handle2: ColliderHandle) {
         let node_id1 = &self.binding[&handle1];
@@ -61,15 +67,21 @@ impl CollisionGraph {
     }
 
     pub fn remove_node(&mut self, handle: ColliderHandle) {
-        let node_id = match self.binding.get(&handle) {
-            Some(&node_id) => node_id,
-            None => panic!("Trying to remove nonexistent node"),
-        };
+        if self.try_remove_node(handle).is_err() {
+            panic!("Trying to remove nonexistent node")
+        }
+    }
+    pub fn try_remove_node(&mut self, handle: ColliderHandle) -> Result<(), Error> {
+        let node_id = self
+            .binding
+            .remove(&handle)
+            .ok_or(Error::GraphDesync(handle))?;
         self.src.remove_node(node_id);
         // in case graph reallocated some other handle to this node
         if let Some(&new_handle) = self.src.node_weight(node_id) {
             self.binding.insert(new_handle, node_id);
         }
+        Ok(())
     }
 
     pub fn edges(
@@ -81,4 +93,20 @@ impl CollisionGraph {
             .edges(node_id)
             .filter_map(move |edge| Some((self.src[edge.target()], edge.weight().as_ref()?)))
     }
+    /// Collisions of the collider with the normal pointing from it towards the other collider.  
+    /// Unlike `edges` the direction of the normal doesn't depend on which collider started the interaction.
+    pub fn collisions_from(
+        &self,
+        handle: ColliderHandle,
+    ) -> impl Iterator<Item = (crate::ColliderHandle, CollisionInfo)> + '_ {
+        let node_id = self.binding[&handle];
+        self.src.edges(node_id).filter_map(move |edge| {
+            let mut info = edge.weight().as_ref()?.collision()?.clone();
+            let (source, _) = self.src.edge_endpoints(edge.id())?;
+            if source != node_id {
+                info.normal = -info.normal;
+            }
+            Some((self.src[edge.target()], info))
+        })
+    }
 }
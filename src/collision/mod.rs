@@ -3,6 +3,7 @@ mod collision_graph;
 mod ray;
 
 pub use self::aabb::{contact_aabb_aabb, intersection_aabb_aabb, CollisionInfo};
+pub use self::aabb::{Contact, ContactPoint};
 pub use self::aabb::{ContactManifold, AABB};
 pub use self::collision_graph::{CollisionGraph, Interaction};
 pub use self::ray::{contact_ray_aabb, Ray, Raycast};
@@ -0,0 +1,271 @@
+use super::collision::CollisionInfo;
+use super::object::{BodyHandle, BodySet, ColliderHandle, ColliderSet};
+use super::world::{is_grounded, snap_to_ground, sweep_x, sweep_y, PhysicsWorld};
+use glam::Vec2;
+
+/// Moves bodies by the requested distance immediately, sliding along the solid colliders on the way.
+/// Meant for kinematic bodies with zero velocity, otherwise `step` moves them on its own.
+#[derive(Debug, Clone)]
+pub struct CharacterController {
+    /// Maximum height of an obstacle the body climbs on instead of being blocked by it, 0 to disable.
+    /// Only grounded bodies step up.
+    pub step_height: f32,
+}
+
+/// Result of `CharacterController::move_and_slide`.
+#[derive(Debug, Clone, Default)]
+pub struct CharacterMotion {
+    /// Distance the body actually moved
+    pub motion: Vec2,
+    /// Collisions of the body after the movement, normal points from the body towards the other collider
+    pub collisions: Vec<(ColliderHandle, CollisionInfo)>,
+    pub on_ground: bool,
+    pub on_ceiling: bool,
+    pub on_wall: bool,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CharacterController {
+    pub fn new() -> Self {
+        Self { step_height: 0. }
+    }
+    pub fn with_step_height(mut self, step_height: f32) -> Self {
+        self.step_height = step_height;
+        self
+    }
+
+    /// Moves the body first on x axis, later on y, same as `step` does.
+    /// With `step_height` set, obstacles low enough are climbed instead of blocking the movement on x axis.
+    /// Grounded bodies with `snap_distance` set are kept on the ground unless moving up.
+    /// Ground, ceiling and wall are told apart using `PhysicsWorld::up`.
+    /// The motion is treated as covering a single `PhysicsWorld::timestep` in the contact data of the events.
+    /// Interactions of the body are updated right away, the events are sent with the next step.
+    pub fn move_and_slide<T: Clone, D: Clone>(
+        &self,
+        handle: BodyHandle,
+        motion: Vec2,
+        world: &mut PhysicsWorld<T, D>,
+        bodies: &mut BodySet<D>,
+        colliders: &ColliderSet<T>,
+    ) -> CharacterMotion {
+        let start = bodies[handle].position;
+        let up = world.up;
+        let grounded = is_grounded(handle, up, bodies, colliders, &world.collision_graph);
+
+        let move_x = sweep_x(
+            handle,
+            motion.x,
+            bodies,
+            colliders,
+            world.pair_filter.as_deref(),
+        );
+        let stepped = grounded
+            && self.step_height > 0.
+            && move_x != motion.x
+            && self.step_up(handle, motion.x, move_x, world, bodies, colliders);
+        if !stepped {
+            bodies[handle].position.x += move_x;
+        }
+        let move_y = sweep_y(
+            handle,
+            motion.y,
+            bodies,
+            colliders,
+            &mut world.collision_graph,
+            world.pair_filter.as_deref(),
+        );
+        bodies[handle].position.y += move_y;
+        // bodies moving up are leaving the ground on purpose
+        if grounded && motion.dot(up) <= 0. {
+            snap_to_ground(
+                handle,
+                up,
+                bodies,
+                colliders,
+                &mut world.collision_graph,
+                world.pair_filter.as_deref(),
+            );
+        }
+
+        bodies[handle].driven_velocity = motion / world.timestep;
+        world.refresh_interactions(handle, bodies, colliders);
+
+        let mut result = CharacterMotion {
+            motion: bodies[handle].position - start,
+            ..Default::default()
+        };
+        for collider_handle in bodies[handle].colliders.iter() {
+            for (other, info) in world.collision_graph.collisions_from(*collider_handle) {
+                if colliders[other].owner == handle {
+                    continue;
+                }
+                let facing = info.normal.dot(up);
+                if facing < -0.5 {
+                    result.on_ground = true;
+                } else if facing > 0.5 {
+                    result.on_ceiling = true;
+                } else {
+                    result.on_wall = true;
+                }
+                result.collisions.push((other, info));
+            }
+        }
+        result
+    }
+
+    // Tries to climb the obstacle that limited the movement on x axis to `blocked_x`.
+    // On success the body is left on top of it, otherwise its position stays unchanged.
+    fn step_up<T: Clone, D: Clone>(
+        &self,
+        handle: BodyHandle,
+        motion_x: f32,
+        blocked_x: f32,
+        world: &mut PhysicsWorld<T, D>,
+        bodies: &mut BodySet<D>,
+        colliders: &ColliderSet<T>,
+    ) -> bool {
+        let start = bodies[handle].position;
+
+        let lift_y = sweep_y(
+            handle,
+            world.up.y.signum() * self.step_height,
+            bodies,
+            colliders,
+            &mut world.collision_graph,
+            world.pair_filter.as_deref(),
+        );
+        bodies[handle].position.y += lift_y;
+
+        let move_x = sweep_x(
+            handle,
+            motion_x,
+            bodies,
+            colliders,
+            world.pair_filter.as_deref(),
+        );
+        if move_x.abs() <= blocked_x.abs() {
+            bodies[handle].position = start;
+            return false;
+        }
+        bodies[handle].position.x += move_x;
+
+        let drop_y = sweep_y(
+            handle,
+            -lift_y,
+            bodies,
+            colliders,
+            &mut world.collision_graph,
+            world.pair_filter.as_deref(),
+        );
+        bodies[handle].position.y += drop_y;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CharacterController;
+    use crate::testing::{add_box, add_static, world_with_floor};
+    use crate::{BodyHandle, World};
+    use glam::Vec2;
+
+    // 16x16 body standing on the ground at `position`
+    fn add_character(world: &mut World<()>, position: Vec2, snap_distance: f32) -> BodyHandle {
+        let (body, _) = add_box(world, position, Vec2::ZERO);
+        world.body_mut(body).unwrap().snap_distance = snap_distance;
+        // registers the contact with the ground
+        world.move_and_slide(&CharacterController::new(), body, Vec2::new(0., 1.));
+        body
+    }
+
+    // floor with a 4 units high step starting at x = 16
+    fn world_with_step() -> World<()> {
+        let mut world = world_with_floor();
+        add_static(&mut world, Vec2::new(24., -10.), Vec2::new(8., 2.));
+        world
+    }
+
+    #[test]
+    fn ground_and_walls_are_reported() {
+        let mut world = world_with_floor();
+        add_static(&mut world, Vec2::new(24., -40.), Vec2::new(8., 32.));
+        let (body, _) = add_box(&mut world, Vec2::new(0., -20.), Vec2::ZERO);
+        let controller = CharacterController::new();
+
+        let result = world.move_and_slide(&controller, body, Vec2::new(0., 10.));
+        assert_eq!(result.motion, Vec2::new(0., 4.));
+        assert!(result.on_ground && !result.on_wall && !result.on_ceiling);
+
+        let result = world.move_and_slide(&controller, body, Vec2::new(16., 0.));
+        assert_eq!(result.motion, Vec2::new(8., 0.));
+        assert!(result.on_ground && result.on_wall);
+    }
+
+    #[test]
+    fn low_obstacle_is_stepped_on() {
+        let mut world = world_with_step();
+        let body = add_character(&mut world, Vec2::new(0., -16.), 0.);
+        let controller = CharacterController::new().with_step_height(6.);
+
+        let result = world.move_and_slide(&controller, body, Vec2::new(16., 0.));
+        assert_eq!(world.body(body).unwrap().position, Vec2::new(16., -20.));
+        assert!(result.on_ground);
+        assert!(!result.on_wall);
+    }
+
+    #[test]
+    fn obstacle_above_step_height_blocks() {
+        let mut world = world_with_step();
+        let body = add_character(&mut world, Vec2::new(0., -16.), 0.);
+        let controller = CharacterController::new().with_step_height(3.);
+
+        let result = world.move_and_slide(&controller, body, Vec2::new(16., 0.));
+        assert_eq!(world.body(body).unwrap().position, Vec2::new(8., -16.));
+        assert!(result.on_wall);
+    }
+
+    // upper ledge on the left ending at x = 0, 3 units lower ground on the right
+    fn world_with_drop() -> World<()> {
+        let mut world = World::new();
+        add_static(&mut world, Vec2::new(-40., 0.), Vec2::new(40., 8.));
+        add_static(&mut world, Vec2::new(40., 3.), Vec2::new(40., 8.));
+        world
+    }
+
+    #[test]
+    fn grounded_body_snaps_down_small_drops() {
+        let mut world = world_with_drop();
+        let body = add_character(&mut world, Vec2::new(-4., -16.), 4.);
+
+        let result = world.move_and_slide(&CharacterController::new(), body, Vec2::new(20., 0.));
+        assert_eq!(world.body(body).unwrap().position, Vec2::new(16., -13.));
+        assert!(result.on_ground);
+    }
+
+    #[test]
+    fn body_without_snap_distance_walks_off() {
+        let mut world = world_with_drop();
+        let body = add_character(&mut world, Vec2::new(-4., -16.), 0.);
+
+        let result = world.move_and_slide(&CharacterController::new(), body, Vec2::new(20., 0.));
+        assert_eq!(world.body(body).unwrap().position, Vec2::new(16., -16.));
+        assert!(!result.on_ground);
+    }
+
+    #[test]
+    fn ground_follows_the_world_up() {
+        let mut world = world_with_floor();
+        world.physics.up = Vec2::new(0., 1.);
+        // hanging below the floor, which is the ground now
+        let (body, _) = add_box(&mut world, Vec2::new(0., 20.), Vec2::ZERO);
+
+        let result = world.move_and_slide(&CharacterController::new(), body, Vec2::new(0., -8.));
+        assert_eq!(result.motion, Vec2::new(0., -4.));
+        assert!(result.on_ground && !result.on_ceiling);
+    }
+}
@@ -0,0 +1,46 @@
+use super::object::{BodyHandle, ColliderHandle};
+use std::fmt;
+
+/// Error returned by the fallible `try_*` variants of the methods that panic otherwise.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The body doesn't exist, e.g. because it was already removed
+    StaleBody(BodyHandle),
+    /// The collider doesn't exist, e.g. because it was already removed
+    StaleCollider(ColliderHandle),
+    /// The body meant to own the collider doesn't exist
+    MissingBody(BodyHandle),
+    /// The collider exists, but the `CollisionGraph` doesn't know about it
+    GraphDesync(ColliderHandle),
+    /// The collider's owner doesn't list it among its colliders, e.g. after changing `Collider::owner` directly
+    OwnerDesync(ColliderHandle),
+    /// The change would leave the collider inside the given solid collider
+    Obstructed(ColliderHandle),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::StaleBody(handle) => write!(f, "body {:?} doesn't exist", handle),
+            Error::StaleCollider(handle) => write!(f, "collider {:?} doesn't exist", handle),
+            Error::MissingBody(handle) => write!(f, "owner {:?} doesn't exist", handle),
+            Error::Obstructed(handle) => write!(f, "obstructed by collider {:?}", handle),
+            Error::OwnerDesync(handle) => {
+                write!(
+                    f,
+                    "collider {:?} is missing from its owner's colliders",
+                    handle
+                )
+            }
+            Error::GraphDesync(handle) => {
+                write!(
+                    f,
+                    "collider {:?} is missing from the collision graph",
+                    handle
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
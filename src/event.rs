@@ -1,16 +1,48 @@
-use super::object::{Collider, ColliderHandle, ColliderState};
+use super::collision::Interaction;
+use super::object::{BodyHandle, Collider, ColliderHandle, ColliderState};
+use glam::Vec2;
 
 /// Event generated by the collision engine.  
-/// In case of an overlap between a solid body and sensor the solid body is guaranteed to be the first handle.
+/// In case of an overlap between a solid body and sensor the solid body is guaranteed to be the first handle,
+/// otherwise the smaller handle comes first.  
+/// Events generated together, e.g. during the step, are sorted by the pair of handles.  
+/// `Ongoing` events are sent every step after `Started` as long as the interaction lasts,
+/// but only if either of the colliders has `ongoing_events` enabled.  
+/// `CollisionStarted` additionally describes the contact at the moment of the impact.
 #[derive(Debug, Clone, Copy)]
 pub enum ContactEvent<T> {
     OverlapStarted(ColliderHandle, ColliderHandle, T, T),
+    OverlapOngoing(ColliderHandle, ColliderHandle, T, T),
     OverlapEnded(ColliderHandle, ColliderHandle, T, T),
-    CollisionStarted(ColliderHandle, ColliderHandle, T, T),
+    CollisionStarted(ColliderHandle, ColliderHandle, T, T, ContactData),
+    CollisionOngoing(ColliderHandle, ColliderHandle, T, T),
     CollisionEnded(ColliderHandle, ColliderHandle, T, T),
 }
 
-impl<T: Copy> ContactEvent<T> {
+/// Contact between the colliders of a `CollisionStarted` event.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContactData {
+    /// Points from the first collider towards the second one
+    pub normal: Vec2,
+    pub depth: f32,
+    pub contact_point: Vec2,
+    /// Velocity of the second body relative to the first one, as they were driven into the contact  
+    /// by their velocity and surface velocity during the step, or by `move_and_slide`
+    pub relative_velocity: Vec2,
+}
+
+/// Event describing contacts between whole bodies, generated when `report_body_contacts` is enabled.  
+/// `Started` is sent when the first pair of their colliders starts interacting,
+/// `Ended` once the last one stops, so moving between adjacent colliders of the other body doesn't flicker.  
+/// The smaller handle always comes first, followed by `user_data` of both bodies at the time the event is generated.  
+/// Bodies removed while in contact report the data they had when they were removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyEvent<D = ()> {
+    BodyContactStarted(BodyHandle, BodyHandle, D, D),
+    BodyContactEnded(BodyHandle, BodyHandle, D, D),
+}
+
+impl<T: Clone> ContactEvent<T> {
     pub fn new(
         h1: ColliderHandle,
         collider1: &Collider<T>,
@@ -19,26 +51,166 @@ impl<T: Copy> ContactEvent<T> {
     ) -> ContactEvent<T> {
         use ColliderState::*;
         match (&collider1.state, &collider2.state) {
-            (Solid, Solid) => {
-                ContactEvent::CollisionStarted(h1, h2, collider1.user_tag, collider2.user_tag)
-            }
-            (Solid, Sensor) => {
-                ContactEvent::OverlapStarted(h1, h2, collider1.user_tag, collider2.user_tag)
-            }
-            (Sensor, Solid) => {
-                ContactEvent::OverlapStarted(h2, h1, collider2.user_tag, collider1.user_tag)
-            }
-            (Sensor, Sensor) => {
-                ContactEvent::OverlapStarted(h1, h2, collider1.user_tag, collider2.user_tag)
-            }
+            (Solid, Solid) => Self::collision(h1, collider1, h2, collider2),
+            _ => Self::overlap(h1, collider1, h2, collider2),
+        }
+    }
+    // started event of the given interaction, which doesn't have to match the states of colliders
+    pub(crate) fn for_interaction(
+        h1: ColliderHandle,
+        collider1: &Collider<T>,
+        h2: ColliderHandle,
+        collider2: &Collider<T>,
+        interaction: &Interaction,
+    ) -> ContactEvent<T> {
+        match interaction {
+            Interaction::Collision(_) => Self::collision(h1, collider1, h2, collider2),
+            Interaction::Overlap => Self::overlap(h1, collider1, h2, collider2),
+        }
+    }
+    fn collision(
+        h1: ColliderHandle,
+        collider1: &Collider<T>,
+        h2: ColliderHandle,
+        collider2: &Collider<T>,
+    ) -> ContactEvent<T> {
+        if h2 < h1 {
+            return Self::collision(h2, collider2, h1, collider1);
+        }
+        ContactEvent::CollisionStarted(
+            h1,
+            h2,
+            collider1.user_tag.clone(),
+            collider2.user_tag.clone(),
+            ContactData::default(),
+        )
+    }
+    fn overlap(
+        h1: ColliderHandle,
+        collider1: &Collider<T>,
+        h2: ColliderHandle,
+        collider2: &Collider<T>,
+    ) -> ContactEvent<T> {
+        use ColliderState::*;
+        match (&collider1.state, &collider2.state) {
+            (Sensor, Solid) => ContactEvent::OverlapStarted(
+                h2,
+                h1,
+                collider2.user_tag.clone(),
+                collider1.user_tag.clone(),
+            ),
+            (Solid, Sensor) => ContactEvent::OverlapStarted(
+                h1,
+                h2,
+                collider1.user_tag.clone(),
+                collider2.user_tag.clone(),
+            ),
+            _ if h2 < h1 => ContactEvent::OverlapStarted(
+                h2,
+                h1,
+                collider2.user_tag.clone(),
+                collider1.user_tag.clone(),
+            ),
+            _ => ContactEvent::OverlapStarted(
+                h1,
+                h2,
+                collider1.user_tag.clone(),
+                collider2.user_tag.clone(),
+            ),
         }
     }
     // changes started events into ended
     pub(crate) fn into_finished(self) -> ContactEvent<T> {
         match self {
             Self::OverlapStarted(h1, h2, t1, t2) => Self::OverlapEnded(h1, h2, t1, t2),
-            Self::CollisionStarted(h1, h2, t1, t2) => Self::CollisionEnded(h1, h2, t1, t2),
+            Self::CollisionStarted(h1, h2, t1, t2, _) => Self::CollisionEnded(h1, h2, t1, t2),
+            _ => self,
+        }
+    }
+    // fills in the contact of collision started events, `contact` is described from the collider `from`
+    pub(crate) fn with_contact(
+        self,
+        from: ColliderHandle,
+        contact: ContactData,
+    ) -> ContactEvent<T> {
+        match self {
+            Self::CollisionStarted(h1, h2, t1, t2, _) if h1 == from => {
+                Self::CollisionStarted(h1, h2, t1, t2, contact)
+            }
+            Self::CollisionStarted(h1, h2, t1, t2, _) => {
+                Self::CollisionStarted(h1, h2, t1, t2, contact.reversed())
+            }
             _ => self,
         }
     }
+    // changes started events into ongoing
+    pub(crate) fn into_ongoing(self) -> ContactEvent<T> {
+        match self {
+            Self::OverlapStarted(h1, h2, t1, t2) => Self::OverlapOngoing(h1, h2, t1, t2),
+            Self::CollisionStarted(h1, h2, t1, t2, _) => Self::CollisionOngoing(h1, h2, t1, t2),
+            _ => self,
+        }
+    }
+}
+
+impl<T> ContactEvent<T> {
+    /// Handles of both colliders, in the same order as in the event.
+    pub fn handles(&self) -> (ColliderHandle, ColliderHandle) {
+        let (h1, h2, _, _) = self.parts();
+        (h1, h2)
+    }
+    /// Whether the collider is one of the two in the event.
+    pub fn involves(&self, handle: ColliderHandle) -> bool {
+        let (h1, h2) = self.handles();
+        h1 == handle || h2 == handle
+    }
+    /// Handle and tag of the collider interacting with the given one, `None` if the event doesn't involve it.
+    pub fn other(&self, handle: ColliderHandle) -> Option<(ColliderHandle, &T)> {
+        let (h1, h2, t1, t2) = self.parts();
+        if h1 == handle {
+            Some((h2, t2))
+        } else if h2 == handle {
+            Some((h1, t1))
+        } else {
+            None
+        }
+    }
+    fn parts(&self) -> (ColliderHandle, ColliderHandle, &T, &T) {
+        match self {
+            Self::OverlapStarted(h1, h2, t1, t2)
+            | Self::OverlapOngoing(h1, h2, t1, t2)
+            | Self::OverlapEnded(h1, h2, t1, t2)
+            | Self::CollisionStarted(h1, h2, t1, t2, _)
+            | Self::CollisionOngoing(h1, h2, t1, t2)
+            | Self::CollisionEnded(h1, h2, t1, t2) => (*h1, *h2, t1, t2),
+        }
+    }
+}
+
+impl ContactData {
+    // the same contact described from the other collider
+    pub(crate) fn reversed(self) -> Self {
+        Self {
+            normal: -self.normal,
+            relative_velocity: -self.relative_velocity,
+            ..self
+        }
+    }
+}
+
+/// Receives events as soon as they are generated, as an alternative to polling `PhysicsWorld::events`.  
+/// Ended events of removed colliders are received during the removal, the rest during the step.  
+/// Every event is still available through `events` afterwards.  
+/// Handlers only have to be `Send`, so the world can still be moved to another thread.
+pub trait EventHandler<T>: Send {
+    fn handle_event(&mut self, event: &ContactEvent<T>);
+}
+
+impl<T, F> EventHandler<T> for F
+where
+    F: FnMut(&ContactEvent<T>) + Send,
+{
+    fn handle_event(&mut self, event: &ContactEvent<T>) {
+        self(event)
+    }
 }
@@ -0,0 +1,110 @@
+use super::object::{Body, Collider};
+use glam::Vec2;
+
+/// How a pair of colliders interacts during the step.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PairMode {
+    /// Interact as usual, solid colliders block each other.
+    Block,
+    /// Only overlap, even if both colliders are solid.
+    Overlap,
+    /// Don't interact at all.
+    Ignore,
+}
+
+/// User supplied hook deciding per step how two colliders interact.
+/// Consulted only for the pairs whose category and mask bits match.
+/// Pairs sensing each other through `sense_bits` only overlap, even if the filter returns `Block`.
+/// Closures with the same signature as `filter` can be used as filters.
+pub trait PairFilter<T, D = ()>: Send {
+    fn filter(
+        &self,
+        collider1: &Collider<T>,
+        body1: &Body<D>,
+        collider2: &Collider<T>,
+        body2: &Body<D>,
+    ) -> PairMode;
+}
+
+impl<T, D, F> PairFilter<T, D> for F
+where
+    F: Fn(&Collider<T>, &Body<D>, &Collider<T>, &Body<D>) -> PairMode + Send,
+{
+    fn filter(
+        &self,
+        collider1: &Collider<T>,
+        body1: &Body<D>,
+        collider2: &Collider<T>,
+        body2: &Body<D>,
+    ) -> PairMode {
+        self(collider1, body1, collider2, body2)
+    }
+}
+
+/// Collision of a kinematic body as seen by `ContactModifier`.  
+/// Left untouched it doesn't affect the body in any way.
+#[derive(Copy, Clone, Debug)]
+pub struct ModifiableContact {
+    /// Points from the body towards the other collider, changing it redirects the response
+    pub normal: Vec2,
+    /// Velocity of the other collider's surface, the body is carried along by it during the next step
+    pub surface_velocity: Vec2,
+    /// Fraction of the body's velocity along the surface removed every step
+    pub friction: f32,
+    /// When set, the body's velocity towards the other collider is reflected and scaled by it
+    pub restitution: Option<f32>,
+}
+
+impl ModifiableContact {
+    pub fn new(normal: Vec2) -> Self {
+        Self {
+            normal,
+            surface_velocity: Vec2::ZERO,
+            friction: 0.,
+            restitution: None,
+        }
+    }
+    // velocity of the body after responding to the contact
+    pub(crate) fn apply(&self, mut velocity: Vec2) -> Vec2 {
+        let normal_speed = velocity.dot(self.normal);
+        if let Some(restitution) = self.restitution {
+            if normal_speed > 0. {
+                velocity -= self.normal * normal_speed * (1. + restitution);
+            }
+        }
+        let tangent_velocity = velocity - self.normal * velocity.dot(self.normal);
+        velocity - tangent_velocity * self.friction.clamp(0., 1.)
+    }
+}
+
+/// User supplied hook adjusting solid collisions of kinematic bodies after every step,
+/// e.g. to build conveyor belts, ice or bounce pads.  
+/// Called for every collision of each kinematic body, with the body passed first.
+/// Adjusted contacts sharing the normal, e.g. with a row of tiles, are averaged into a single surface.
+/// Closures with the same signature as `modify` can be used as modifiers.
+pub trait ContactModifier<T, D = ()>: Send {
+    fn modify(
+        &self,
+        collider1: &Collider<T>,
+        body1: &Body<D>,
+        collider2: &Collider<T>,
+        body2: &Body<D>,
+        contact: &mut ModifiableContact,
+    );
+}
+
+impl<T, D, F> ContactModifier<T, D> for F
+where
+    F: Fn(&Collider<T>, &Body<D>, &Collider<T>, &Body<D>, &mut ModifiableContact) + Send,
+{
+    fn modify(
+        &self,
+        collider1: &Collider<T>,
+        body1: &Body<D>,
+        collider2: &Collider<T>,
+        body2: &Body<D>,
+        contact: &mut ModifiableContact,
+    ) {
+        self(collider1, body1, collider2, body2, contact)
+    }
+}
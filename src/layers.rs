@@ -0,0 +1,139 @@
+/// Registry of up to 32 named collision layers along with the matrices deciding how they interact.
+/// Every layer interacts with all the others until configured otherwise.
+/// Meant to be configured once, `ColliderDesc::with_layer` copies the current settings into the collider.
+#[derive(Debug, Clone)]
+pub struct CollisionLayers {
+    names: Vec<String>,
+    // bit j of row i is set if layers i and j interact
+    interactions: [u32; 32],
+    // bit j of row i is set if layers i and j only overlap, even when both colliders are solid
+    sensing: [u32; 32],
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CollisionLayers {
+    pub fn new() -> Self {
+        Self {
+            names: Vec::new(),
+            interactions: [u32::MAX; 32],
+            sensing: [0; 32],
+        }
+    }
+
+    /// Registers the layer and returns its category bits, registering the same name twice returns the same bits.
+    /// Panics if all 32 layers are already taken.
+    pub fn add_layer(&mut self, name: &str) -> u32 {
+        if let Some(bits) = self.bits(name) {
+            return bits;
+        }
+        if self.names.len() == 32 {
+            panic!("Can't add layer {:?}, all 32 layers are taken", name)
+        }
+        self.names.push(name.to_owned());
+        1 << (self.names.len() - 1)
+    }
+    /// Category bits of the layer, `None` if it wasn't registered.
+    pub fn bits(&self, name: &str) -> Option<u32> {
+        self.index(name).map(|index| 1 << index)
+    }
+    /// Layers the given one interacts with, to be used as `mask_bits`.
+    /// Panics if the layer wasn't registered.
+    pub fn mask(&self, name: &str) -> u32 {
+        self.interactions[self.expect_index(name)]
+    }
+    /// Layers the given one only overlaps with, to be used as `sense_bits`.
+    /// Panics if the layer wasn't registered.
+    pub fn sense_mask(&self, name: &str) -> u32 {
+        self.sensing[self.expect_index(name)]
+    }
+
+    /// Sets whether the colliders on both layers interact at all, in both directions.
+    /// Panics if either layer wasn't registered.
+    pub fn set_interaction(&mut self, layer1: &str, layer2: &str, interact: bool) {
+        let (index1, index2) = (self.expect_index(layer1), self.expect_index(layer2));
+        set_symmetric(&mut self.interactions, index1, index2, interact);
+    }
+    /// Sets whether the solid colliders on both layers only overlap instead of blocking each other.
+    /// Panics if either layer wasn't registered.
+    pub fn set_sensing(&mut self, layer1: &str, layer2: &str, sense_only: bool) {
+        let (index1, index2) = (self.expect_index(layer1), self.expect_index(layer2));
+        set_symmetric(&mut self.sensing, index1, index2, sense_only);
+    }
+
+    fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|layer| layer == name)
+    }
+    fn expect_index(&self, name: &str) -> usize {
+        match self.index(name) {
+            Some(index) => index,
+            None => panic!("Unknown collision layer {:?}", name),
+        }
+    }
+}
+
+fn set_symmetric(matrix: &mut [u32; 32], index1: usize, index2: usize, value: bool) {
+    for (row, column) in [(index1, index2), (index2, index1)] {
+        if value {
+            matrix[row] |= 1 << column;
+        } else {
+            matrix[row] &= !(1 << column);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CollisionLayers;
+
+    fn layers() -> CollisionLayers {
+        let mut layers = CollisionLayers::new();
+        for name in ["ground", "player", "ghost"] {
+            layers.add_layer(name);
+        }
+        layers
+    }
+
+    #[test]
+    fn readding_a_layer_returns_its_bits() {
+        let mut layers = layers();
+        assert_eq!(layers.bits("player"), Some(0b10));
+        assert_eq!(layers.add_layer("player"), 0b10);
+        assert_eq!(layers.add_layer("enemy"), 0b1000);
+        assert_eq!(layers.bits("missing"), None);
+    }
+
+    #[test]
+    fn interactions_are_symmetric() {
+        let mut layers = layers();
+        assert_eq!(layers.mask("ghost"), u32::MAX);
+
+        layers.set_interaction("ghost", "ground", false);
+        assert_eq!(layers.mask("ghost"), !0b1);
+        assert_eq!(layers.mask("ground"), !0b100);
+        assert_eq!(layers.mask("player"), u32::MAX);
+
+        layers.set_interaction("ground", "ghost", true);
+        assert_eq!(layers.mask("ghost"), u32::MAX);
+        assert_eq!(layers.mask("ground"), u32::MAX);
+    }
+
+    #[test]
+    fn sensing_is_symmetric() {
+        let mut layers = layers();
+        assert_eq!(layers.sense_mask("player"), 0);
+
+        layers.set_sensing("player", "ghost", true);
+        assert_eq!(layers.sense_mask("player"), 0b100);
+        assert_eq!(layers.sense_mask("ghost"), 0b10);
+        assert_eq!(layers.sense_mask("ground"), 0);
+
+        layers.set_sensing("ghost", "player", false);
+        assert_eq!(layers.sense_mask("player"), 0);
+        assert_eq!(layers.sense_mask("ghost"), 0);
+    }
+}
@@ -1,11 +1,25 @@
 mod collision;
+mod controller;
+mod error;
 mod event;
+mod hooks;
+mod layers;
 mod object;
+mod probe;
+mod simple;
+#[cfg(test)]
+mod testing;
 mod world;
 
 pub use self::collision::*;
-pub use self::event::ContactEvent;
+pub use self::controller::{CharacterController, CharacterMotion};
+pub use self::error::Error;
+pub use self::event::{BodyEvent, ContactData, ContactEvent, EventHandler};
+pub use self::hooks::{ContactModifier, ModifiableContact, PairFilter, PairMode};
+pub use self::layers::CollisionLayers;
 pub use self::object::*;
+pub use self::probe::Side;
+pub use self::simple::World;
 pub use self::world::*;
 
 // TODO: tests once public API is more defined...
@@ -1,3 +1,4 @@
+use super::body_set::BodyHandle;
 use super::collider_set::ColliderHandle;
 use glam::Vec2;
 
@@ -5,7 +6,7 @@ use glam::Vec2;
 ///  
 /// It functions as a container for colliders.
 #[derive(Clone, Debug)]
-pub struct Body {
+pub struct Body<D = ()> {
     pub position: Vec2,
     /// static body CAN have velocity - it just behaves as if it had infinite mass  
     /// (this might change with introduction of kinematic body that pushes other objects)  
@@ -15,23 +16,63 @@ pub struct Body {
     pub status: BodyStatus,
     /// Whether colliders of the same body should collide
     pub self_collide: bool,
+    /// Distance the body gets pulled down by to stay on the ground it stood on during the last step, 0 to disable
+    pub snap_distance: f32,
+    /// Disabled bodies don't move and their colliders are skipped by the step and queries.  
+    /// Their interactions end during the next step.
+    pub enabled: bool,
+    /// Free slot for the user, e.g. to map the body back to its entity, cloned into `BodyEvent`s
+    pub user_data: D,
     // cached list of colliders belonging to body
     pub(crate) colliders: Vec<ColliderHandle>,
+    // bodies this one doesn't interact with, kept in sync on both sides
+    pub(crate) ignored: Vec<BodyHandle>,
     // the distance body will want to cover during the next step
     pub(crate) movement: Vec2,
+    // position at the start of the last step, used for interpolation
+    pub(crate) previous_position: Vec2,
+    // velocity of the surfaces carrying the body, set by contact modification
+    pub(crate) surface_velocity: Vec2,
+    // velocity the body was driven with during the last step or `move_and_slide`, reported in `ContactData`
+    pub(crate) driven_velocity: Vec2,
 }
 
-impl Body {
+impl<D: Default> Body<D> {
     pub fn new(position: Vec2, velocity: Vec2, status: BodyStatus, self_collide: bool) -> Self {
+        Self::new_with_data(position, velocity, status, self_collide, D::default())
+    }
+}
+
+impl<D> Body<D> {
+    // `new` for data without a default value, used by `BodyDesc`
+    pub(crate) fn new_with_data(
+        position: Vec2,
+        velocity: Vec2,
+        status: BodyStatus,
+        self_collide: bool,
+        user_data: D,
+    ) -> Self {
         Self {
             position,
             velocity,
             status,
             self_collide,
+            snap_distance: 0.,
+            enabled: true,
+            user_data,
             colliders: Vec::new(),
+            ignored: Vec::new(),
             movement: Vec2::ZERO,
+            previous_position: position,
+            surface_velocity: Vec2::ZERO,
+            driven_velocity: Vec2::ZERO,
         }
     }
+    /// Position blended between the last two physics steps.  
+    /// `alpha` is usually `PhysicsWorld::alpha`, 0 being the previous and 1 the current position.
+    pub fn interpolated_position(&self, alpha: f32) -> Vec2 {
+        self.previous_position.lerp(self.position, alpha)
+    }
 }
 /// Status of the body, determines how it's affected by other bodies.
 #[derive(Copy, Clone, Debug)]
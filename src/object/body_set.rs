@@ -8,17 +8,17 @@ use std::ops::{Index, IndexMut};
 pub struct BodyHandle(generational_arena::Index);
 
 /// Container for bodies, removal is currently performed through `PhysicsWorld`, but access and modification is possible through this structure
-pub struct BodySet {
-    bodies: Arena<Body>,
+pub struct BodySet<D = ()> {
+    bodies: Arena<Body<D>>,
 }
 
-impl Default for BodySet {
+impl<D> Default for BodySet<D> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl BodySet {
+impl<D> BodySet<D> {
     pub fn new() -> Self {
         Self {
             bodies: Arena::with_capacity(16),
@@ -26,44 +26,44 @@ impl BodySet {
     }
 
     /// Inserts a new body into the world and returns it's unique handle.
-    pub fn insert(&mut self, body: Body) -> BodyHandle {
+    pub fn insert(&mut self, body: Body<D>) -> BodyHandle {
         let key = self.bodies.insert(body);
         BodyHandle(key)
     }
 
-    pub fn get(&self, handle: BodyHandle) -> Option<&Body> {
+    pub fn get(&self, handle: BodyHandle) -> Option<&Body<D>> {
         self.bodies.get(handle.0)
     }
-    pub fn get_mut(&mut self, handle: BodyHandle) -> Option<&mut Body> {
+    pub fn get_mut(&mut self, handle: BodyHandle) -> Option<&mut Body<D>> {
         self.bodies.get_mut(handle.0)
     }
-    pub fn iter(&self) -> impl Iterator<Item = (crate::BodyHandle, &Body)> {
+    pub fn iter(&self) -> impl Iterator<Item = (crate::BodyHandle, &Body<D>)> {
         self.bodies
             .iter()
             .map(|(index, body)| (BodyHandle(index), body))
     }
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = (crate::BodyHandle, &mut Body)> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (crate::BodyHandle, &mut Body<D>)> {
         self.bodies
             .iter_mut()
             .map(|(index, body)| (BodyHandle(index), body))
     }
-    pub(crate) fn internal_remove(&mut self, handle: BodyHandle) -> Body {
+    pub(crate) fn internal_remove(&mut self, handle: BodyHandle) -> Body<D> {
         self.bodies
             .remove(handle.0)
             .expect("Tried to remove nonexistent body")
     }
 }
 
-impl Index<BodyHandle> for BodySet {
-    type Output = Body;
+impl<D> Index<BodyHandle> for BodySet<D> {
+    type Output = Body<D>;
 
-    fn index(&self, index: BodyHandle) -> &Body {
+    fn index(&self, index: BodyHandle) -> &Body<D> {
         &self.bodies[index.0]
     }
 }
 
-impl IndexMut<BodyHandle> for BodySet {
-    fn index_mut(&mut self, index: BodyHandle) -> &mut Body {
+impl<D> IndexMut<BodyHandle> for BodySet<D> {
+    fn index_mut(&mut self, index: BodyHandle) -> &mut Body<D> {
         &mut self.bodies[index.0]
     }
 }
@@ -1,15 +1,19 @@
 pub use super::super::collision::AABB;
+use super::super::layers::CollisionLayers;
 pub use super::{Body, BodyHandle, BodyStatus, Collider, ColliderState};
 use glam::Vec2;
 
 /// Builder for the `Body`. Start with `new`, finish with `build`.
 #[derive(Debug, Clone)]
-pub struct BodyDesc {
+pub struct BodyDesc<D = ()> {
     pub position: Vec2,
 
     pub velocity: Vec2,
     pub status: BodyStatus,
     pub self_collide: bool,
+    pub snap_distance: f32,
+    pub enabled: bool,
+    pub user_data: D,
 }
 
 impl Default for BodyDesc {
@@ -19,14 +23,21 @@ impl Default for BodyDesc {
 }
 
 impl BodyDesc {
+    /// Starts without user data, `with_user_data` sets its type.
     pub fn new() -> Self {
         Self {
             position: Vec2::ZERO,
             velocity: Vec2::ZERO,
             status: BodyStatus::Kinematic,
             self_collide: true,
+            snap_distance: 0.,
+            enabled: true,
+            user_data: (),
         }
     }
+}
+
+impl<D> BodyDesc<D> {
     pub fn with_position(mut self, position: Vec2) -> Self {
         self.position = position;
         self
@@ -43,8 +54,36 @@ impl BodyDesc {
         self.self_collide = check;
         self
     }
-    pub fn build(self) -> Body {
-        Body::new(self.position, self.velocity, self.status, self.self_collide)
+    pub fn with_snap_distance(mut self, snap_distance: f32) -> Self {
+        self.snap_distance = snap_distance;
+        self
+    }
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+    pub fn with_user_data<E>(self, user_data: E) -> BodyDesc<E> {
+        BodyDesc {
+            position: self.position,
+            velocity: self.velocity,
+            status: self.status,
+            self_collide: self.self_collide,
+            snap_distance: self.snap_distance,
+            enabled: self.enabled,
+            user_data,
+        }
+    }
+    pub fn build(self) -> Body<D> {
+        let mut body = Body::new_with_data(
+            self.position,
+            self.velocity,
+            self.status,
+            self.self_collide,
+            self.user_data,
+        );
+        body.snap_distance = self.snap_distance;
+        body.enabled = self.enabled;
+        body
     }
 }
 
@@ -57,11 +96,16 @@ pub struct ColliderDesc<T> {
 
     pub category_bits: u32,
     pub mask_bits: u32,
+    pub sense_bits: u32,
+    pub ongoing_events: bool,
+    pub enabled: bool,
+    pub emit_collision_events: bool,
+    pub emit_overlap_events: bool,
 
     pub user_tag: T,
 }
 
-impl<T: Copy> ColliderDesc<T> {
+impl<T> ColliderDesc<T> {
     pub fn new(shape: AABB, user_tag: T) -> Self {
         Self {
             shape,
@@ -69,6 +113,11 @@ impl<T: Copy> ColliderDesc<T> {
             state: ColliderState::Solid,
             category_bits: 1,
             mask_bits: u32::MAX,
+            sense_bits: 0,
+            ongoing_events: false,
+            enabled: true,
+            emit_collision_events: true,
+            emit_overlap_events: true,
             user_tag,
         }
     }
@@ -92,12 +141,42 @@ impl<T: Copy> ColliderDesc<T> {
         self.mask_bits = mask_bits;
         self
     }
+    pub fn with_sense_mask(mut self, sense_bits: u32) -> Self {
+        self.sense_bits = sense_bits;
+        self
+    }
+    /// Sets category, mask and sense bits according to the layer registered in `layers`.  
+    /// Panics if the layer wasn't registered.
+    pub fn with_layer(mut self, layers: &CollisionLayers, name: &str) -> Self {
+        self.category_bits = layers
+            .bits(name)
+            .unwrap_or_else(|| panic!("Unknown collision layer {:?}", name));
+        self.mask_bits = layers.mask(name);
+        self.sense_bits = layers.sense_mask(name);
+        self
+    }
+    pub fn ongoing_events(mut self, report: bool) -> Self {
+        self.ongoing_events = report;
+        self
+    }
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+    pub fn emit_collision_events(mut self, emit: bool) -> Self {
+        self.emit_collision_events = emit;
+        self
+    }
+    pub fn emit_overlap_events(mut self, emit: bool) -> Self {
+        self.emit_overlap_events = emit;
+        self
+    }
     pub fn with_tag(mut self, user_tag: T) -> Self {
         self.user_tag = user_tag;
         self
     }
     pub fn build(self, owner: BodyHandle) -> Collider<T> {
-        Collider::new(
+        let mut collider = Collider::new(
             self.shape,
             self.offset,
             self.state,
@@ -105,6 +184,12 @@ impl<T: Copy> ColliderDesc<T> {
             self.mask_bits,
             self.user_tag,
             owner,
-        )
+        );
+        collider.sense_bits = self.sense_bits;
+        collider.ongoing_events = self.ongoing_events;
+        collider.enabled = self.enabled;
+        collider.emit_collision_events = self.emit_collision_events;
+        collider.emit_overlap_events = self.emit_overlap_events;
+        collider
     }
 }
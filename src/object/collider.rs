@@ -16,10 +16,20 @@ pub struct Collider<T> {
     pub category_bits: u32,
     /// Bodies only collide if both of their masks match
     pub mask_bits: u32,
+    /// Categories the collider only overlaps with, even when both colliders are solid
+    pub sense_bits: u32,
     /// User supplied tag for identification
     pub user_tag: T,
     /// Body who owns the collider
     pub owner: BodyHandle,
+    /// Whether to send `Ongoing` events every step the collider keeps interacting with another one
+    pub ongoing_events: bool,
+    /// Disabled colliders are skipped by the step and queries, their interactions end during the next step
+    pub enabled: bool,
+    /// Whether collision events are sent, both colliders have to agree for the events to be sent
+    pub emit_collision_events: bool,
+    /// Whether overlap events are sent, both colliders have to agree for the events to be sent
+    pub emit_overlap_events: bool,
 }
 
 impl<T> Collider<T> {
@@ -40,6 +50,11 @@ impl<T> Collider<T> {
             mask_bits,
             user_tag,
             owner,
+            sense_bits: 0,
+            ongoing_events: false,
+            enabled: true,
+            emit_collision_events: true,
+            emit_overlap_events: true,
         }
     }
     pub fn overlaps_aabb(&self, own_position: Vec2, position: Vec2, half_exts: Vec2) -> bool {
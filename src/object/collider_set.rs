@@ -28,17 +28,28 @@ impl<T> ColliderSet<T> {
     /// Inserts a new collider into the Set if it's associated body exists.  
     /// In the case where body doesn't exist returns `None`.  
     /// Currently requires `PhysicsWorld` as an argument to add a node to `CollisionGraph`.
-    pub fn insert(
+    pub fn insert<D>(
         &mut self,
         collider: Collider<T>,
-        bodies: &mut BodySet,
-        world: &mut crate::PhysicsWorld<T>,
+        bodies: &mut BodySet<D>,
+        world: &mut crate::PhysicsWorld<T, D>,
     ) -> Option<ColliderHandle> {
-        let body = bodies.get_mut(collider.owner)?;
+        self.try_insert(collider, bodies, world).ok()
+    }
+    /// Same as `insert`, but returns `Error::MissingBody` if the associated body doesn't exist.
+    pub fn try_insert<D>(
+        &mut self,
+        collider: Collider<T>,
+        bodies: &mut BodySet<D>,
+        world: &mut crate::PhysicsWorld<T, D>,
+    ) -> Result<ColliderHandle, crate::Error> {
+        let body = bodies
+            .get_mut(collider.owner)
+            .ok_or(crate::Error::MissingBody(collider.owner))?;
         let key = self.colliders.insert(collider);
         world.collision_graph.add_node(ColliderHandle(key));
         body.colliders.push(ColliderHandle(key));
-        Some(ColliderHandle(key))
+        Ok(ColliderHandle(key))
     }
 
     pub fn get(&self, handle: ColliderHandle) -> Option<&Collider<T>> {
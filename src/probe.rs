@@ -0,0 +1,196 @@
+use super::collision::{Ray, Raycast};
+use super::object::{BodyHandle, BodySet, ColliderHandle, ColliderSet, ColliderState};
+use super::world::PhysicsWorld;
+use glam::Vec2;
+
+// How far inside the body the ground probe starts.
+const PROBE_INSET: f32 = 0.01;
+
+/// Side of the body to probe, left being negative x.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    pub fn sign(self) -> f32 {
+        match self {
+            Side::Left => -1.,
+            Side::Right => 1.,
+        }
+    }
+}
+
+/// Helpers answering common questions of platformer AI, built on top of `project_ray_excluding` and `overlap_test_excluding`.
+/// Only solid colliders of other, not ignored bodies are taken into account, "down" is the opposite of `PhysicsWorld::up`.
+impl<T: Clone, D: Clone> PhysicsWorld<T, D> {
+    /// Casts a ray down from the front edge of the body's bottom and returns the ground it hit within `depth`.
+    /// `None` means the body is about to walk off a ledge, walls touching the front edge don't count as ground.
+    pub fn ground_ahead(
+        &self,
+        handle: BodyHandle,
+        side: Side,
+        depth: f32,
+        collision_mask: u32,
+        bodies: &BodySet<D>,
+        colliders: &ColliderSet<T>,
+    ) -> Option<(ColliderHandle, Raycast)> {
+        let (min, max) = solid_bounds(handle, bodies, colliders)?;
+        let down = -self.up.y.signum();
+        // start slightly inside the body, so neither the ground it stands on nor a wall it touches is hit at toi 0
+        let origin = Vec2::new(
+            front_x(min, max, side) - side.sign() * PROBE_INSET,
+            if down > 0. { max.y } else { min.y } - down * PROBE_INSET,
+        );
+        let ray = Ray {
+            origin,
+            dir: Vec2::new(0., down),
+            toi: depth + PROBE_INSET,
+        };
+        let up = self.up;
+        let (collider, mut raycast) =
+            self.closest_hit(handle, &ray, collision_mask, bodies, colliders, |raycast| {
+                raycast.normal.dot(up) > 0.5
+            })?;
+        raycast.toi = (raycast.toi - PROBE_INSET).max(0.);
+        Some((collider, raycast))
+    }
+
+    /// Returns a collider touching the given side of the body, or at most `reach` away from it.
+    /// Colliders only touching the body's top or bottom edge aren't treated as walls.
+    pub fn wall_contact(
+        &self,
+        handle: BodyHandle,
+        side: Side,
+        reach: f32,
+        collision_mask: u32,
+        bodies: &BodySet<D>,
+        colliders: &ColliderSet<T>,
+    ) -> Option<ColliderHandle> {
+        let (min, max) = solid_bounds(handle, bodies, colliders)?;
+        // thin strip along the side, slightly shorter than the body to skip the floor and ceiling
+        let half_exts = Vec2::new(reach * 0.5, (max.y - min.y) * 0.5 - 0.01);
+        let position = Vec2::new(
+            front_x(min, max, side) + side.sign() * reach * 0.5,
+            (min.y + max.y) * 0.5,
+        );
+        self.overlap_test_excluding(
+            handle,
+            position,
+            half_exts,
+            collision_mask,
+            bodies,
+            colliders,
+        )
+        .find(|h| is_solid(*h, colliders))
+    }
+
+    /// Looks for the top edge of a collider `reach` in front of the body, at most `height` below the body's top.
+    /// Returns the point on the edge that can be grabbed.
+    /// Nothing is returned when the probed column is blocked at the body's top, e.g. by a wall taller than the body.
+    #[allow(clippy::too_many_arguments)]
+    pub fn ledge_ahead(
+        &self,
+        handle: BodyHandle,
+        side: Side,
+        reach: f32,
+        height: f32,
+        collision_mask: u32,
+        bodies: &BodySet<D>,
+        colliders: &ColliderSet<T>,
+    ) -> Option<Vec2> {
+        let (min, max) = solid_bounds(handle, bodies, colliders)?;
+        let down = -self.up.y.signum();
+        let origin = Vec2::new(
+            front_x(min, max, side) + side.sign() * reach,
+            if down > 0. { min.y } else { max.y },
+        );
+        let ray = Ray {
+            origin,
+            dir: Vec2::new(0., down),
+            toi: height,
+        };
+        let (_, raycast) =
+            self.closest_hit(handle, &ray, collision_mask, bodies, colliders, |_| true)?;
+        // ray starting inside a collider means it's a wall, not a ledge
+        if raycast.toi <= 0. {
+            return None;
+        }
+        Some(origin + ray.dir * raycast.toi)
+    }
+
+    fn closest_hit(
+        &self,
+        handle: BodyHandle,
+        ray: &Ray,
+        collision_mask: u32,
+        bodies: &BodySet<D>,
+        colliders: &ColliderSet<T>,
+        accept: impl Fn(&Raycast) -> bool,
+    ) -> Option<(ColliderHandle, Raycast)> {
+        self.project_ray_excluding(handle, ray, collision_mask, bodies, colliders)
+            .filter(|(h, raycast)| is_solid(*h, colliders) && accept(raycast))
+            .min_by(|(_, a), (_, b)| {
+                a.toi
+                    .partial_cmp(&b.toi)
+                    .unwrap_or(std::cmp::Ordering::Less)
+            })
+    }
+}
+
+// Minimum and maximum corner of the box enclosing body's solid colliders.
+fn solid_bounds<T, D>(
+    handle: BodyHandle,
+    bodies: &BodySet<D>,
+    colliders: &ColliderSet<T>,
+) -> Option<(Vec2, Vec2)> {
+    let body = bodies.get(handle)?;
+    body.colliders
+        .iter()
+        .map(|h| &colliders[*h])
+        .filter(|collider| matches!(collider.state, ColliderState::Solid))
+        .map(|collider| {
+            let center = body.position + collider.offset;
+            (
+                center - collider.shape.half_exts,
+                center + collider.shape.half_exts,
+            )
+        })
+        .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))
+}
+
+fn front_x(min: Vec2, max: Vec2, side: Side) -> f32 {
+    match side {
+        Side::Left => min.x,
+        Side::Right => max.x,
+    }
+}
+
+// Only solid colliders count as ground, walls and ledges.
+fn is_solid<T>(collider_handle: ColliderHandle, colliders: &ColliderSet<T>) -> bool {
+    matches!(colliders[collider_handle].state, ColliderState::Solid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Side;
+    use crate::testing::{add_box, add_static};
+    use crate::World;
+    use glam::Vec2;
+
+    #[test]
+    fn wall_touching_front_edge_isnt_ground() {
+        let mut world: World<()> = World::new();
+        // floor ends at x = 4, the wall starts at x = 8
+        add_static(&mut world, Vec2::new(-18., 0.), Vec2::new(22., 8.));
+        add_static(&mut world, Vec2::new(16., -8.), Vec2::new(8., 40.));
+        let (body, _) = add_box(&mut world, Vec2::new(0., -16.), Vec2::ZERO);
+
+        assert!(world
+            .ground_ahead(body, Side::Right, 4., u32::MAX)
+            .is_none());
+        let (_, raycast) = world.ground_ahead(body, Side::Left, 4., u32::MAX).unwrap();
+        assert_eq!(raycast.normal, Vec2::new(0., -1.));
+    }
+}
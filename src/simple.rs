@@ -0,0 +1,276 @@
+use super::collision::{Ray, Raycast, AABB};
+use super::controller::{CharacterController, CharacterMotion};
+use super::error::Error;
+use super::event::{BodyEvent, ContactEvent};
+use super::object::{Body, BodyHandle, BodySet, Collider, ColliderHandle, ColliderSet};
+use super::probe::Side;
+use super::world::PhysicsWorld;
+use glam::Vec2;
+
+/// All-in-one alternative to using `PhysicsWorld`, `BodySet` and `ColliderSet` separately.
+/// The parts stay public, anything not covered by the shortcuts can be done through them directly.
+pub struct World<T, D = ()> {
+    pub physics: PhysicsWorld<T, D>,
+    pub bodies: BodySet<D>,
+    pub colliders: ColliderSet<T>,
+}
+
+impl<T: Clone, D: Clone> Default for World<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, D: Clone> World<T, D> {
+    pub fn new() -> Self {
+        Self {
+            physics: PhysicsWorld::new(),
+            bodies: BodySet::new(),
+            colliders: ColliderSet::new(),
+        }
+    }
+
+    pub fn add_body(&mut self, body: Body<D>) -> BodyHandle {
+        self.bodies.insert(body)
+    }
+    /// Returns `None` if the collider's owner doesn't exist.
+    pub fn add_collider(&mut self, collider: Collider<T>) -> Option<ColliderHandle> {
+        self.colliders
+            .insert(collider, &mut self.bodies, &mut self.physics)
+    }
+    /// Same as `add_collider`, but returns `Error::MissingBody` instead of `None`.
+    pub fn try_add_collider(&mut self, collider: Collider<T>) -> Result<ColliderHandle, Error> {
+        self.colliders
+            .try_insert(collider, &mut self.bodies, &mut self.physics)
+    }
+    /// See `PhysicsWorld::remove_body`.
+    pub fn remove_body(&mut self, handle: BodyHandle) {
+        self.physics
+            .remove_body(handle, &mut self.bodies, &mut self.colliders)
+    }
+    /// See `PhysicsWorld::remove_collider`.
+    pub fn remove_collider(&mut self, handle: ColliderHandle) {
+        self.physics
+            .remove_collider(handle, &mut self.bodies, &mut self.colliders)
+    }
+
+    /// See `PhysicsWorld::try_remove_body`.
+    pub fn try_remove_body(&mut self, handle: BodyHandle) -> Result<(), Error> {
+        self.physics
+            .try_remove_body(handle, &mut self.bodies, &mut self.colliders)
+    }
+    /// See `PhysicsWorld::try_remove_collider`.
+    pub fn try_remove_collider(&mut self, handle: ColliderHandle) -> Result<(), Error> {
+        self.physics
+            .try_remove_collider(handle, &mut self.bodies, &mut self.colliders)
+    }
+    /// See `PhysicsWorld::teleport`.
+    pub fn teleport(
+        &mut self,
+        handle: BodyHandle,
+        position: Vec2,
+        depenetrate: bool,
+    ) -> Result<(), Error> {
+        self.physics.teleport(
+            handle,
+            position,
+            depenetrate,
+            &mut self.bodies,
+            &self.colliders,
+        )
+    }
+    /// See `PhysicsWorld::ignore_pair`.
+    pub fn ignore_pair(&mut self, body1: BodyHandle, body2: BodyHandle) -> Result<(), Error> {
+        self.physics.ignore_pair(body1, body2, &mut self.bodies)
+    }
+    /// See `PhysicsWorld::unignore_pair`.
+    pub fn unignore_pair(&mut self, body1: BodyHandle, body2: BodyHandle) -> Result<(), Error> {
+        self.physics.unignore_pair(body1, body2, &mut self.bodies)
+    }
+    /// See `PhysicsWorld::resize_collider`.
+    pub fn resize_collider(
+        &mut self,
+        handle: ColliderHandle,
+        shape: AABB,
+        offset: Vec2,
+    ) -> Result<(), Error> {
+        self.physics
+            .resize_collider(handle, shape, offset, &self.bodies, &mut self.colliders)
+    }
+    /// See `PhysicsWorld::reparent_collider`.
+    pub fn reparent_collider(
+        &mut self,
+        handle: ColliderHandle,
+        new_owner: BodyHandle,
+        new_offset: Vec2,
+    ) -> Result<(), Error> {
+        self.physics.reparent_collider(
+            handle,
+            new_owner,
+            new_offset,
+            &mut self.bodies,
+            &mut self.colliders,
+        )
+    }
+
+    pub fn body(&self, handle: BodyHandle) -> Option<&Body<D>> {
+        self.bodies.get(handle)
+    }
+    pub fn body_mut(&mut self, handle: BodyHandle) -> Option<&mut Body<D>> {
+        self.bodies.get_mut(handle)
+    }
+    pub fn collider(&self, handle: ColliderHandle) -> Option<&Collider<T>> {
+        self.colliders.get(handle)
+    }
+    pub fn collider_mut(&mut self, handle: ColliderHandle) -> Option<&mut Collider<T>> {
+        self.colliders.get_mut(handle)
+    }
+
+    pub fn step(&mut self, dt: f32) {
+        self.physics.step(dt, &mut self.bodies, &mut self.colliders)
+    }
+    /// See `PhysicsWorld::try_step`.
+    pub fn try_step(&mut self, dt: f32) -> Result<(), Error> {
+        self.physics
+            .try_step(dt, &mut self.bodies, &mut self.colliders)
+    }
+    /// See `PhysicsWorld::advance`.
+    pub fn advance(&mut self, frame_dt: f32) -> u32 {
+        self.physics
+            .advance(frame_dt, &mut self.bodies, &mut self.colliders)
+    }
+    pub fn alpha(&self) -> f32 {
+        self.physics.alpha()
+    }
+    pub fn events(&self) -> &Vec<ContactEvent<T>> {
+        self.physics.events()
+    }
+    pub fn body_events(&self) -> &Vec<BodyEvent<D>> {
+        self.physics.body_events()
+    }
+
+    /// See `PhysicsWorld::overlap_test`.
+    pub fn overlap_test(
+        &self,
+        position: Vec2,
+        half_exts: Vec2,
+        collision_mask: u32,
+    ) -> impl Iterator<Item = ColliderHandle> + '_ {
+        self.physics.overlap_test(
+            position,
+            half_exts,
+            collision_mask,
+            &self.bodies,
+            &self.colliders,
+        )
+    }
+    /// See `PhysicsWorld::project_ray`.
+    pub fn project_ray<'a>(
+        &'a self,
+        ray: &'a Ray,
+        collision_mask: u32,
+    ) -> impl Iterator<Item = (ColliderHandle, Raycast)> + 'a {
+        self.physics
+            .project_ray(ray, collision_mask, &self.bodies, &self.colliders)
+    }
+    /// See `PhysicsWorld::overlap_test_excluding`.
+    pub fn overlap_test_excluding(
+        &self,
+        exclude: BodyHandle,
+        position: Vec2,
+        half_exts: Vec2,
+        collision_mask: u32,
+    ) -> impl Iterator<Item = ColliderHandle> + '_ {
+        self.physics.overlap_test_excluding(
+            exclude,
+            position,
+            half_exts,
+            collision_mask,
+            &self.bodies,
+            &self.colliders,
+        )
+    }
+    /// See `PhysicsWorld::project_ray_excluding`.
+    pub fn project_ray_excluding<'a>(
+        &'a self,
+        exclude: BodyHandle,
+        ray: &'a Ray,
+        collision_mask: u32,
+    ) -> impl Iterator<Item = (ColliderHandle, Raycast)> + 'a {
+        self.physics.project_ray_excluding(
+            exclude,
+            ray,
+            collision_mask,
+            &self.bodies,
+            &self.colliders,
+        )
+    }
+    /// See `PhysicsWorld::ground_ahead`.
+    pub fn ground_ahead(
+        &self,
+        handle: BodyHandle,
+        side: Side,
+        depth: f32,
+        collision_mask: u32,
+    ) -> Option<(ColliderHandle, Raycast)> {
+        self.physics.ground_ahead(
+            handle,
+            side,
+            depth,
+            collision_mask,
+            &self.bodies,
+            &self.colliders,
+        )
+    }
+    /// See `PhysicsWorld::wall_contact`.
+    pub fn wall_contact(
+        &self,
+        handle: BodyHandle,
+        side: Side,
+        reach: f32,
+        collision_mask: u32,
+    ) -> Option<ColliderHandle> {
+        self.physics.wall_contact(
+            handle,
+            side,
+            reach,
+            collision_mask,
+            &self.bodies,
+            &self.colliders,
+        )
+    }
+    /// See `PhysicsWorld::ledge_ahead`.
+    pub fn ledge_ahead(
+        &self,
+        handle: BodyHandle,
+        side: Side,
+        reach: f32,
+        height: f32,
+        collision_mask: u32,
+    ) -> Option<Vec2> {
+        self.physics.ledge_ahead(
+            handle,
+            side,
+            reach,
+            height,
+            collision_mask,
+            &self.bodies,
+            &self.colliders,
+        )
+    }
+    /// See `CharacterController::move_and_slide`.
+    pub fn move_and_slide(
+        &mut self,
+        controller: &CharacterController,
+        handle: BodyHandle,
+        motion: Vec2,
+    ) -> CharacterMotion {
+        controller.move_and_slide(
+            handle,
+            motion,
+            &mut self.physics,
+            &mut self.bodies,
+            &self.colliders,
+        )
+    }
+}
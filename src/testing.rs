@@ -0,0 +1,72 @@
+// Fixtures shared by the unit tests of all modules.
+use crate::builder::{BodyDesc, ColliderDesc};
+use crate::{BodyHandle, ColliderHandle, ContactEvent, World, AABB};
+use glam::Vec2;
+
+pub(crate) const DT: f32 = 1. / 60.;
+
+pub(crate) fn aabb(half_x: f32, half_y: f32) -> AABB {
+    AABB {
+        half_exts: Vec2::new(half_x, half_y),
+    }
+}
+
+// Static body with a single solid collider.
+pub(crate) fn add_static<D: Clone + Default>(
+    world: &mut World<(), D>,
+    position: Vec2,
+    half_exts: Vec2,
+) -> (BodyHandle, ColliderHandle) {
+    let body = world.add_body(
+        BodyDesc::new()
+            .with_position(position)
+            .make_static()
+            .with_user_data(D::default())
+            .build(),
+    );
+    let collider = world
+        .add_collider(ColliderDesc::new(AABB { half_exts }, ()).build(body))
+        .unwrap();
+    (body, collider)
+}
+
+// 16x16 kinematic body with a single solid collider.
+pub(crate) fn add_box<D: Clone + Default>(
+    world: &mut World<(), D>,
+    position: Vec2,
+    velocity: Vec2,
+) -> (BodyHandle, ColliderHandle) {
+    let body = world.add_body(
+        BodyDesc::new()
+            .with_position(position)
+            .with_velocity(velocity)
+            .with_user_data(D::default())
+            .build(),
+    );
+    let collider = world
+        .add_collider(ColliderDesc::new(aabb(8., 8.), ()).build(body))
+        .unwrap();
+    (body, collider)
+}
+
+// 160 units wide floor centered at the origin, its top edge is at y = -8.
+pub(crate) fn world_with_floor() -> World<()> {
+    let mut world = World::new();
+    add_static(&mut world, Vec2::ZERO, Vec2::new(80., 8.));
+    world
+}
+
+// Row of 16x16 tiles centered at the given x, their top edges are at y = -8.
+pub(crate) fn world_with_tiles(xs: &[f32]) -> World<()> {
+    let mut world = World::new();
+    for x in xs {
+        add_static(&mut world, Vec2::new(*x, 0.), Vec2::new(8., 8.));
+    }
+    world
+}
+
+// Performs a single step and returns the events it generated.
+pub(crate) fn step_events<D: Clone>(world: &mut World<(), D>) -> Vec<ContactEvent<()>> {
+    world.step(DT);
+    world.events().clone()
+}
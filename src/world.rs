@@ -1,80 +1,142 @@
-use super::collision::{CollisionGraph, CollisionInfo, Interaction, Ray, Raycast};
-use super::event::ContactEvent;
+use super::collision::{CollisionGraph, CollisionInfo, Interaction, Ray, Raycast, AABB};
+use super::error::Error;
+use super::event::{BodyEvent, ContactData, ContactEvent, EventHandler};
+use super::hooks::{ContactModifier, ModifiableContact, PairFilter, PairMode};
 use super::object::{
     collision_manifold, is_colliding, is_penetrating, Body, BodyHandle, BodySet, BodyStatus,
     Collider, ColliderHandle, ColliderSet, ColliderState,
 };
 use glam::Vec2;
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
 
-/// T - User supplied type used as a tag, present in all events
-pub struct PhysicsWorld<T> {
+/// T - User supplied type used as a tag, cloned into all events  
+/// D - User supplied type of `Body::user_data`, cloned into `BodyEvent`s
+pub struct PhysicsWorld<T, D = ()> {
     pub collision_graph: CollisionGraph,
+    /// Length of a single step performed by `advance`, has to be positive
+    pub timestep: f32,
+    /// Upper limit of steps performed during a single `advance` call, the leftover time is dropped
+    pub max_steps: u32,
+    /// Direction treated as up by ground snapping, `CharacterController` and the probing helpers, defaults to negative y.  
+    /// Only `(0, -1)` and `(0, 1)` are supported, the solver only tells ground from ceiling along y axis.
+    pub up: Vec2,
+    /// Maximum distance a kinematic body stuck inside a solid collider is pushed out by during a single step, 0 to disable.  
+    /// Until it's pushed out completely the body can move freely, except deeper into the collider.
+    pub max_depenetration: f32,
+    /// Whether to generate `BodyEvent`s, disabled by default
+    pub report_body_contacts: bool,
     pub(crate) events: Vec<ContactEvent<T>>,
-    removal_events: Vec<ContactEvent<T>>,
+    // events generated outside of `step`, sent with the next one
+    pending_events: Vec<ContactEvent<T>>,
+    body_events: Vec<BodyEvent<D>>,
+    // sorted pairs of bodies in contact as of the last step
+    body_contacts: Vec<(BodyHandle, BodyHandle)>,
+    // user data of the bodies removed while in contact, for their `BodyContactEnded` events
+    removed_body_data: Vec<(BodyHandle, D)>,
+    event_handler: Option<Box<dyn EventHandler<T>>>,
+    pub(crate) pair_filter: Option<Box<dyn PairFilter<T, D>>>,
+    contact_modifier: Option<Box<dyn ContactModifier<T, D>>>,
     body_handles: Vec<BodyHandle>,
+    // bodies standing on the ground at the start of the step
+    snapping: Vec<BodyHandle>,
+    // time not yet consumed by `advance`
+    accumulator: f32,
 }
 
-impl<T: Copy> Default for PhysicsWorld<T> {
+impl<T: Clone, D: Clone> Default for PhysicsWorld<T, D> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T: Copy> PhysicsWorld<T> {
+impl<T: Clone, D: Clone> PhysicsWorld<T, D> {
     pub fn new() -> Self {
         Self {
             collision_graph: CollisionGraph::with_capacity(128, 16),
+            timestep: 1. / 60.,
+            max_steps: 8,
+            up: Vec2::new(0., -1.),
+            max_depenetration: f32::INFINITY,
+            report_body_contacts: false,
             events: Vec::with_capacity(16),
-            removal_events: Vec::with_capacity(8),
+            pending_events: Vec::with_capacity(8),
+            body_events: Vec::new(),
+            body_contacts: Vec::new(),
+            removed_body_data: Vec::new(),
+            event_handler: None,
+            pair_filter: None,
+            contact_modifier: None,
             body_handles: Vec::with_capacity(16),
+            snapping: Vec::with_capacity(4),
+            accumulator: 0.,
         }
     }
     /// Panics if there's no collider associated with the handle.  
-    /// When collider has active collisions/overlaps the Ended event is scheduled to be sent next frame.
+    /// When collider has active collisions/overlaps the Ended event is scheduled to be sent next frame.  
+    /// The registered `EventHandler` receives it right away.
     pub fn remove_collider(
         &mut self,
         handle: ColliderHandle,
-        bodies: &mut BodySet,
+        bodies: &mut BodySet<D>,
         colliders: &mut ColliderSet<T>,
     ) {
-        #[cfg(debug_assertions)]
-        if colliders.get(handle).is_none() {
-            panic!("Trying to delete nonexistent collider {:?}", handle)
+        if let Err(error) = self.try_remove_collider(handle, bodies, colliders) {
+            panic!("Trying to delete collider {:?}: {}", handle, error)
         }
+    }
+    /// Same as `remove_collider`, but returns an error instead of panicking if the handle is stale
+    /// or the collider is out of sync with its owner or the `CollisionGraph`, nothing is removed then.
+    pub fn try_remove_collider(
+        &mut self,
+        handle: ColliderHandle,
+        bodies: &mut BodySet<D>,
+        colliders: &mut ColliderSet<T>,
+    ) -> Result<(), Error> {
+        let owner = colliders
+            .get(handle)
+            .ok_or(Error::StaleCollider(handle))?
+            .owner;
+        // if owner doesn't exist it's assumed both collider and body are getting removed
+        let owned_index = match bodies.get(owner) {
+            Some(body) => Some(
+                body.colliders
+                    .iter()
+                    .position(|owned_handle| *owned_handle == handle)
+                    .ok_or(Error::OwnerDesync(handle))?,
+            ),
+            None => None,
+        };
+        self.collision_graph.try_get_node_index(handle)?;
         let collider = colliders.internal_remove(handle);
         let collision_graph = &mut self.collision_graph;
-        let removal_events = &mut self.removal_events;
+        let pending_events = &mut self.pending_events;
+        let first_event = pending_events.len();
 
         // schedule collision/overlap ended events
-        let node_index = collision_graph.get_node_index(handle);
-        for node_index_other in collision_graph.src.neighbors(node_index) {
-            let handle_other = *collision_graph
-                .src
-                .node_weight(node_index_other)
-                .expect("remove_collider: other node missing");
+        for (handle_other, interaction) in collision_graph.edges(handle) {
             let collider_other = &colliders[handle_other];
-            let event =
-                ContactEvent::new(handle, &collider, handle_other, collider_other).into_finished();
-            removal_events.push(event);
+            if !emits_events(&collider, collider_other, interaction) {
+                continue;
+            }
+            let event = ContactEvent::for_interaction(
+                handle,
+                &collider,
+                handle_other,
+                collider_other,
+                interaction,
+            )
+            .into_finished();
+            pending_events.push(event);
         }
         collision_graph.remove_node(handle);
+        pending_events[first_event..].sort_by_key(ContactEvent::handles);
+        notify(&mut self.event_handler, &pending_events[first_event..]);
 
-        // if owner doesn't exist it's assumed both collider and body are getting removed
-        if let Some(body) = bodies.get_mut(collider.owner) {
-            let index = body
-                .colliders
-                .iter()
-                .position(|owned_handle| *owned_handle == handle);
-            if let Some(index) = index {
-                body.colliders.swap_remove(index);
-            } else {
-                #[cfg(debug_assertions)]
-                panic!(
-                    "Body {:?} didn't know about {:?} collider",
-                    collider.owner, handle
-                )
-            }
+        if let Some(index) = owned_index {
+            bodies[owner].colliders.swap_remove(index);
         }
+        Ok(())
     }
     /// Panics if there's no body associated with the handle.  
     /// All associated colliders are also removed.
@@ -82,17 +144,162 @@ impl<T: Copy> PhysicsWorld<T> {
     pub fn remove_body(
         &mut self,
         handle: BodyHandle,
-        bodies: &mut BodySet,
+        bodies: &mut BodySet<D>,
         colliders: &mut ColliderSet<T>,
     ) {
-        #[cfg(debug_assertions)]
+        if let Err(error) = self.try_remove_body(handle, bodies, colliders) {
+            panic!("Trying to delete body {:?}: {}", handle, error)
+        }
+    }
+    /// Same as `remove_body`, but returns an error instead of panicking if any handle is stale.  
+    /// The body and the rest of its colliders are removed even if one of the colliders fails.
+    pub fn try_remove_body(
+        &mut self,
+        handle: BodyHandle,
+        bodies: &mut BodySet<D>,
+        colliders: &mut ColliderSet<T>,
+    ) -> Result<(), Error> {
         if bodies.get(handle).is_none() {
-            panic!("Trying to delete nonexistent body {:?}", handle)
+            return Err(Error::StaleBody(handle));
         }
         let body = bodies.internal_remove(handle);
+        for other in body.ignored.iter() {
+            if let Some(other) = bodies.get_mut(*other) {
+                other.ignored.retain(|ignored| *ignored != handle);
+            }
+        }
+        if self
+            .body_contacts
+            .iter()
+            .any(|(h1, h2)| *h1 == handle || *h2 == handle)
+        {
+            self.removed_body_data.push((handle, body.user_data));
+        }
+        let mut result = Ok(());
         for collider_handle in body.colliders.into_iter() {
-            self.remove_collider(collider_handle, bodies, colliders);
+            let removal = self.try_remove_collider(collider_handle, bodies, colliders);
+            result = result.and(removal);
         }
+        result
+    }
+    /// Moves the body to `position` right away, without treating the jump as a movement.  
+    /// With `depenetrate` set the body is pushed out of the solid colliders it lands in.  
+    /// Interactions are re-evaluated at the destination, events of the ones left behind are sent with the next step.
+    pub fn teleport(
+        &mut self,
+        handle: BodyHandle,
+        position: Vec2,
+        depenetrate: bool,
+        bodies: &mut BodySet<D>,
+        colliders: &ColliderSet<T>,
+    ) -> Result<(), Error> {
+        let body = bodies.get_mut(handle).ok_or(Error::StaleBody(handle))?;
+        body.position = position;
+        if depenetrate {
+            let filter = self.pair_filter.as_deref();
+            resolve_penetration(handle, f32::INFINITY, bodies, colliders, filter);
+        }
+        // nothing to interpolate between and the jump isn't an impact
+        let body = &mut bodies[handle];
+        body.previous_position = body.position;
+        body.driven_velocity = Vec2::ZERO;
+
+        self.refresh_interactions(handle, bodies, colliders);
+        Ok(())
+    }
+    /// Stops all interactions between colliders of the two bodies until `unignore_pair` is called,
+    /// e.g. so a bullet doesn't hit the gun that fired it.  
+    /// Current interactions end during the next step. Probing helpers skip the ignored body as well.
+    pub fn ignore_pair(
+        &mut self,
+        body1: BodyHandle,
+        body2: BodyHandle,
+        bodies: &mut BodySet<D>,
+    ) -> Result<(), Error> {
+        check_pair(body1, body2, bodies)?;
+        if body1 != body2 && !bodies[body1].ignored.contains(&body2) {
+            bodies[body1].ignored.push(body2);
+            bodies[body2].ignored.push(body1);
+        }
+        Ok(())
+    }
+    /// Lets the two bodies interact again after `ignore_pair`.
+    pub fn unignore_pair(
+        &mut self,
+        body1: BodyHandle,
+        body2: BodyHandle,
+        bodies: &mut BodySet<D>,
+    ) -> Result<(), Error> {
+        check_pair(body1, body2, bodies)?;
+        bodies[body1].ignored.retain(|ignored| *ignored != body2);
+        bodies[body2].ignored.retain(|ignored| *ignored != body1);
+        Ok(())
+    }
+    /// Changes the shape and offset of the collider, e.g. when a character crouches.  
+    /// A solid collider isn't resized if it would end up inside another solid collider, which is returned as `Error::Obstructed`.  
+    /// Interactions are updated right away, the events are sent with the next step.
+    pub fn resize_collider(
+        &mut self,
+        handle: ColliderHandle,
+        shape: AABB,
+        offset: Vec2,
+        bodies: &BodySet<D>,
+        colliders: &mut ColliderSet<T>,
+    ) -> Result<(), Error> {
+        let collider = colliders.get(handle).ok_or(Error::StaleCollider(handle))?;
+        let owner = collider.owner;
+        if bodies.get(owner).is_none() {
+            return Err(Error::MissingBody(owner));
+        }
+        self.collision_graph.try_get_node_index(handle)?;
+
+        let previous = colliders[handle].clone();
+        let collider = &mut colliders[handle];
+        collider.shape = shape;
+        collider.offset = offset;
+        if let Some(obstacle) = obstruction(
+            handle,
+            &previous,
+            bodies,
+            colliders,
+            self.pair_filter.as_deref(),
+        ) {
+            colliders[handle] = previous;
+            return Err(Error::Obstructed(obstacle));
+        }
+
+        self.refresh_interactions(owner, bodies, colliders);
+        Ok(())
+    }
+    /// Moves the collider to another body, placing it at `new_offset` from the body's position.  
+    /// Interactions are updated right away, the events are sent with the next step.  
+    /// Disable `self_collide` of the new owner if the collider shouldn't interact with its other colliders.
+    pub fn reparent_collider(
+        &mut self,
+        handle: ColliderHandle,
+        new_owner: BodyHandle,
+        new_offset: Vec2,
+        bodies: &mut BodySet<D>,
+        colliders: &mut ColliderSet<T>,
+    ) -> Result<(), Error> {
+        let collider = colliders
+            .get_mut(handle)
+            .ok_or(Error::StaleCollider(handle))?;
+        if bodies.get(new_owner).is_none() {
+            return Err(Error::StaleBody(new_owner));
+        }
+        self.collision_graph.try_get_node_index(handle)?;
+
+        if let Some(body) = bodies.get_mut(collider.owner) {
+            body.colliders
+                .retain(|owned_handle| *owned_handle != handle);
+        }
+        bodies[new_owner].colliders.push(handle);
+        collider.owner = new_owner;
+        collider.offset = new_offset;
+
+        self.refresh_interactions(new_owner, bodies, colliders);
+        Ok(())
     }
 
     /// Interactions are defined per collider.  
@@ -130,67 +337,303 @@ impl<T: Copy> PhysicsWorld<T> {
         position: Vec2,
         half_exts: Vec2,
         collision_mask: u32,
-        bodies: &'a BodySet,
+        bodies: &'a BodySet<D>,
         colliders: &'a ColliderSet<T>,
     ) -> impl Iterator<Item = ColliderHandle> + 'a {
-        // TODO: Use broadphase
-        bodies
-            .iter()
-            .flat_map(|(_, body)| body.colliders.iter().map(move |h| (*h, body.position)))
-            .filter(move |(h, _)| (colliders[*h].category_bits & collision_mask) != 0)
-            .filter_map(move |(h, body_pos)| {
+        queried_colliders(None, collision_mask, bodies, colliders).filter_map(
+            move |(h, body_pos)| {
                 if colliders[h].overlaps_aabb(body_pos, position, half_exts) {
                     Some(h)
                 } else {
                     None
                 }
-            })
+            },
+        )
+    }
+    /// Same as `overlap_test`, but skips the colliders of the given body and of the bodies it ignores.
+    pub fn overlap_test_excluding<'a>(
+        &self,
+        exclude: BodyHandle,
+        position: Vec2,
+        half_exts: Vec2,
+        collision_mask: u32,
+        bodies: &'a BodySet<D>,
+        colliders: &'a ColliderSet<T>,
+    ) -> impl Iterator<Item = ColliderHandle> + 'a {
+        queried_colliders(Some(exclude), collision_mask, bodies, colliders).filter_map(
+            move |(h, body_pos)| {
+                if colliders[h].overlaps_aabb(body_pos, position, half_exts) {
+                    Some(h)
+                } else {
+                    None
+                }
+            },
+        )
     }
     /// Returns an iterator to `ColliderHandle`'s of colliders overlapping with given ray.  
     pub fn project_ray<'a>(
         &self,
         ray: &'a Ray,
         collision_mask: u32,
-        bodies: &'a BodySet,
+        bodies: &'a BodySet<D>,
         colliders: &'a ColliderSet<T>,
     ) -> impl Iterator<Item = (ColliderHandle, Raycast)> + 'a {
-        // TODO: Use broadphase
-        bodies
-            .iter()
-            .flat_map(|(_, body)| body.colliders.iter().map(move |h| (*h, body.position)))
-            .filter(move |(h, _)| (colliders[*h].category_bits & collision_mask) != 0)
-            .filter_map(move |(h, pos)| {
+        queried_colliders(None, collision_mask, bodies, colliders).filter_map(move |(h, pos)| {
+            colliders[h]
+                .ray_contact(pos, ray)
+                .map(|raycast| (h, raycast))
+        })
+    }
+    /// Same as `project_ray`, but skips the colliders of the given body and of the bodies it ignores.
+    pub fn project_ray_excluding<'a>(
+        &self,
+        exclude: BodyHandle,
+        ray: &'a Ray,
+        collision_mask: u32,
+        bodies: &'a BodySet<D>,
+        colliders: &'a ColliderSet<T>,
+    ) -> impl Iterator<Item = (ColliderHandle, Raycast)> + 'a {
+        queried_colliders(Some(exclude), collision_mask, bodies, colliders).filter_map(
+            move |(h, pos)| {
                 colliders[h]
                     .ray_contact(pos, ray)
                     .map(|raycast| (h, raycast))
-            })
+            },
+        )
     }
     pub fn events(&self) -> &Vec<ContactEvent<T>> {
         &self.events
     }
+    /// Contacts between bodies that started or ended during the last step, see `report_body_contacts`.  
+    /// Unlike `events` it takes into account colliders with events disabled.
+    pub fn body_events(&self) -> &Vec<BodyEvent<D>> {
+        &self.body_events
+    }
+    /// Registers the handler receiving every event as soon as it's generated, replacing the previous one.  
+    /// Closures taking `&ContactEvent<T>` can be used as handlers.
+    pub fn set_event_handler(&mut self, handler: impl EventHandler<T> + 'static) {
+        self.event_handler = Some(Box::new(handler));
+    }
+    /// Unregisters the handler, returning it.
+    pub fn take_event_handler(&mut self) -> Option<Box<dyn EventHandler<T>>> {
+        self.event_handler.take()
+    }
+    /// Registers the filter deciding how pairs of colliders interact, replacing the previous one.  
+    /// Closures can be used as filters, see `PairFilter`.
+    pub fn set_pair_filter(&mut self, filter: impl PairFilter<T, D> + 'static) {
+        self.pair_filter = Some(Box::new(filter));
+    }
+    /// Unregisters the filter, returning it.
+    pub fn take_pair_filter(&mut self) -> Option<Box<dyn PairFilter<T, D>>> {
+        self.pair_filter.take()
+    }
+    /// Registers the hook adjusting collisions of kinematic bodies, replacing the previous one.  
+    /// Closures can be used as modifiers, see `ContactModifier`.
+    pub fn set_contact_modifier(&mut self, modifier: impl ContactModifier<T, D> + 'static) {
+        self.contact_modifier = Some(Box::new(modifier));
+    }
+    /// Unregisters the modifier, returning it.
+    pub fn take_contact_modifier(&mut self) -> Option<Box<dyn ContactModifier<T, D>>> {
+        self.contact_modifier.take()
+    }
+
+    /// Accumulates `frame_dt` and performs as many steps of length `timestep` as fit in it.  
+    /// Events of all the performed steps are available through `events` afterwards.  
+    /// Returns the number of performed steps.  
+    /// Panics if `timestep` isn't positive.
+    pub fn advance(
+        &mut self,
+        frame_dt: f32,
+        bodies: &mut BodySet<D>,
+        colliders: &mut ColliderSet<T>,
+    ) -> u32 {
+        assert!(
+            self.timestep > 0.,
+            "advance: timestep has to be positive, got {}",
+            self.timestep
+        );
+        self.events.clear();
+        self.body_events.clear();
+        self.accumulator += frame_dt;
+
+        let mut steps = 0;
+        while self.accumulator >= self.timestep {
+            if steps == self.max_steps {
+                log::debug!("advance: dropping {}s of simulation", self.accumulator);
+                self.accumulator %= self.timestep;
+                break;
+            }
+            self.simulate(self.timestep, bodies, colliders);
+            self.accumulator -= self.timestep;
+            steps += 1;
+        }
+        steps
+    }
+    /// How far the simulation is between the last and the next step, in range 0 to 1.  
+    /// Meant to be passed to `Body::interpolated_position` when rendering.
+    pub fn alpha(&self) -> f32 {
+        if self.timestep > 0. {
+            self.accumulator / self.timestep
+        } else {
+            0.
+        }
+    }
 
-    pub fn step(&mut self, dt: f32, bodies: &mut BodySet, colliders: &mut ColliderSet<T>) {
+    pub fn step(&mut self, dt: f32, bodies: &mut BodySet<D>, colliders: &mut ColliderSet<T>) {
         self.events.clear();
-        self.events.append(&mut self.removal_events);
+        self.body_events.clear();
+        self.simulate(dt, bodies, colliders);
+    }
+    /// Same as `step`, but first makes sure the bodies, colliders and the `CollisionGraph` agree with each other.  
+    /// Nothing is simulated if they don't, as the step would panic.
+    pub fn try_step(
+        &mut self,
+        dt: f32,
+        bodies: &mut BodySet<D>,
+        colliders: &mut ColliderSet<T>,
+    ) -> Result<(), Error> {
+        self.check_consistency(bodies, colliders)?;
+        self.step(dt, bodies, colliders);
+        Ok(())
+    }
+    // Finds the first dangling handle the step would trip on.
+    fn check_consistency(
+        &self,
+        bodies: &BodySet<D>,
+        colliders: &ColliderSet<T>,
+    ) -> Result<(), Error> {
+        for (handle, collider) in colliders.iter() {
+            match bodies.get(collider.owner) {
+                None => return Err(Error::MissingBody(collider.owner)),
+                Some(body) if !body.colliders.contains(&handle) => {
+                    return Err(Error::OwnerDesync(handle))
+                }
+                Some(_) => {}
+            }
+            self.collision_graph.try_get_node_index(handle)?;
+        }
+        for (_, body) in bodies.iter() {
+            if let Some(handle) = body.colliders.iter().find(|h| colliders.get(**h).is_none()) {
+                return Err(Error::StaleCollider(*handle));
+            }
+        }
+        Ok(())
+    }
+
+    /// Brings the interactions of body's colliders up to date after it was moved outside of `step`.  
+    /// Generated events are sent with the next step.
+    pub(crate) fn refresh_interactions(
+        &mut self,
+        handle: BodyHandle,
+        bodies: &BodySet<D>,
+        colliders: &ColliderSet<T>,
+    ) {
+        let body = bodies.get(handle).expect("Refreshing nonexistent body");
+        let collision_graph = &mut self.collision_graph;
+        let first_event = self.pending_events.len();
+
+        let filter = self.pair_filter.as_deref();
+        register_edges(handle, bodies, colliders, collision_graph, filter);
+
+        let mut edge_ids: Vec<_> = body
+            .colliders
+            .iter()
+            .flat_map(|h| {
+                let node_id = collision_graph.get_node_index(*h);
+                collision_graph.src.edges(node_id).map(|edge| edge.id())
+            })
+            .collect();
+        // edges between colliders of the same body are visited twice
+        edge_ids.sort();
+        edge_ids.dedup();
+
+        let mut removed_edges = vec![];
+        for edge_id in edge_ids {
+            if !update_interaction(
+                edge_id,
+                bodies,
+                colliders,
+                collision_graph,
+                filter,
+                &mut self.pending_events,
+                false,
+            ) {
+                removed_edges.push(collision_graph.src.edge_endpoints(edge_id).unwrap());
+            }
+        }
+        remove_edges(collision_graph, removed_edges);
+        self.pending_events[first_event..].sort_by_key(ContactEvent::handles);
+        notify(&mut self.event_handler, &self.pending_events[first_event..]);
+    }
+
+    fn simulate(&mut self, dt: f32, bodies: &mut BodySet<D>, colliders: &mut ColliderSet<T>) {
+        self.events.append(&mut self.pending_events);
         self.body_handles.clear();
+        self.snapping.clear();
 
         let collision_graph = &mut self.collision_graph;
         let events = &mut self.events;
         let body_handles = &mut self.body_handles;
+        let snapping = &mut self.snapping;
+        let filter = self.pair_filter.as_deref();
+        let up = self.up;
 
         body_handles.extend(bodies.iter().map(|(h, _)| h));
+        snapping.extend(body_handles.iter().copied().filter(|h| {
+            let body = &bodies[*h];
+            matches!(body.status, BodyStatus::Kinematic)
+                && body.snap_distance > 0.
+                && is_grounded(*h, up, bodies, colliders, collision_graph)
+        }));
 
         // compute the new maximum movement for every body
         for (_, body) in bodies.iter_mut() {
+            body.previous_position = body.position;
             if let BodyStatus::Kinematic = body.status {
-                body.movement = body.velocity * dt;
+                body.driven_velocity = body.velocity + body.surface_velocity;
+                body.movement = body.driven_velocity * dt;
+            } else {
+                body.driven_velocity = body.velocity;
+            }
+            body.surface_velocity = Vec2::ZERO;
+        }
+
+        // push the bodies out of the solids they're stuck in, e.g. after spawning inside one
+        if self.max_depenetration > 0. {
+            for handle in body_handles.iter() {
+                let body = &bodies[*handle];
+                if body.enabled && matches!(body.status, BodyStatus::Kinematic) {
+                    resolve_penetration(*handle, self.max_depenetration, bodies, colliders, filter);
+                }
+            }
+        }
+
+        step_x(bodies, colliders, filter, body_handles);
+        step_y(bodies, colliders, collision_graph, filter, body_handles);
+
+        // bodies moving up are leaving the ground on purpose
+        for handle in snapping.iter() {
+            if bodies[*handle].movement.dot(up) <= 0. {
+                snap_to_ground(*handle, up, bodies, colliders, collision_graph, filter);
             }
         }
 
-        step_x(bodies, colliders, body_handles);
-        step_y(bodies, colliders, collision_graph, body_handles);
+        // pending events were already passed to the handler when generated
+        let first_event = events.len();
+        describe_collisions(bodies, colliders, collision_graph, filter, events);
+        events[first_event..].sort_by_key(ContactEvent::handles);
+        notify(&mut self.event_handler, &events[first_event..]);
 
-        describe_collisions(bodies, colliders, collision_graph, events);
+        if let Some(modifier) = self.contact_modifier.as_deref() {
+            respond_to_contacts(modifier, bodies, colliders, collision_graph, body_handles);
+        }
+
+        if self.report_body_contacts {
+            self.update_body_contacts(bodies, colliders);
+        } else {
+            self.body_contacts.clear();
+            self.removed_body_data.clear();
+        }
 
         // for (h1, _h2, manifold) in manifolds.iter() {
         //     let body = bodies.get_mut(*h1).expect("Body missing post collision");
@@ -203,172 +646,578 @@ impl<T: Copy> PhysicsWorld<T> {
     }
 }
 
-fn step_x<T>(bodies: &mut BodySet, colliders: &mut ColliderSet<T>, body_handles: &[BodyHandle]) {
+impl<T, D: Clone> PhysicsWorld<T, D> {
+    // Compares the bodies in contact with the previous step, generating `BodyEvent`s for the differences.
+    fn update_body_contacts(&mut self, bodies: &BodySet<D>, colliders: &ColliderSet<T>) {
+        let graph = &self.collision_graph.src;
+        let mut current: Vec<_> = graph
+            .edge_references()
+            .filter(|edge| edge.weight().is_some())
+            .map(|edge| {
+                (
+                    colliders[graph[edge.source()]].owner,
+                    colliders[graph[edge.target()]].owner,
+                )
+            })
+            .filter(|(owner1, owner2)| owner1 != owner2)
+            .map(|(owner1, owner2)| (owner1.min(owner2), owner1.max(owner2)))
+            .collect();
+        current.sort();
+        current.dedup();
+
+        let previous = std::mem::replace(&mut self.body_contacts, current);
+        let current = &self.body_contacts;
+        let removed = std::mem::take(&mut self.removed_body_data);
+        let data_of = |handle: BodyHandle| match bodies.get(handle) {
+            Some(body) => body.user_data.clone(),
+            None => removed
+                .iter()
+                .find(|(removed, _)| *removed == handle)
+                .map(|(_, data)| data.clone())
+                .expect("Body in contact was removed without keeping its data"),
+        };
+        self.body_events.extend(
+            previous
+                .iter()
+                .filter(|pair| current.binary_search(pair).is_err())
+                .map(|(h1, h2)| BodyEvent::BodyContactEnded(*h1, *h2, data_of(*h1), data_of(*h2))),
+        );
+        self.body_events.extend(
+            current
+                .iter()
+                .filter(|pair| previous.binary_search(pair).is_err())
+                .map(|(h1, h2)| {
+                    BodyEvent::BodyContactStarted(*h1, *h2, data_of(*h1), data_of(*h2))
+                }),
+        );
+    }
+}
+
+fn check_pair<D>(body1: BodyHandle, body2: BodyHandle, bodies: &BodySet<D>) -> Result<(), Error> {
+    match (bodies.get(body1), bodies.get(body2)) {
+        (None, _) => Err(Error::StaleBody(body1)),
+        (_, None) => Err(Error::StaleBody(body2)),
+        _ => Ok(()),
+    }
+}
+
+// Enabled colliders matching the mask along with their owners' positions, candidates for the queries.
+// Colliders of `exclude` and of the bodies it ignores are skipped.
+fn queried_colliders<'a, T, D>(
+    exclude: Option<BodyHandle>,
+    collision_mask: u32,
+    bodies: &'a BodySet<D>,
+    colliders: &'a ColliderSet<T>,
+) -> impl Iterator<Item = (ColliderHandle, Vec2)> + 'a {
+    let ignored = exclude
+        .and_then(|h| bodies.get(h))
+        .map(|body| &body.ignored);
+    // TODO: Use broadphase
+    bodies
+        .iter()
+        .filter(|(_, body)| body.enabled)
+        .filter(move |(h, _)| {
+            Some(*h) != exclude && !ignored.is_some_and(|ignored| ignored.contains(h))
+        })
+        .flat_map(|(_, body)| body.colliders.iter().map(move |h| (*h, body.position)))
+        .filter(move |(h, _)| colliders[*h].enabled)
+        .filter(move |(h, _)| (colliders[*h].category_bits & collision_mask) != 0)
+}
+
+fn notify<T>(handler: &mut Option<Box<dyn EventHandler<T>>>, events: &[ContactEvent<T>]) {
+    if let Some(handler) = handler {
+        events.iter().for_each(|event| handler.handle_event(event));
+    }
+}
+
+fn step_x<T, D>(
+    bodies: &mut BodySet<D>,
+    colliders: &ColliderSet<T>,
+    filter: Option<&dyn PairFilter<T, D>>,
+    body_handles: &[BodyHandle],
+) {
     for body1_handle in body_handles {
         let body1 = bodies.get(*body1_handle).expect("Collider without a body");
-        let mut move_x = body1.movement.x;
 
-        if let BodyStatus::Static = body1.status {
+        // disabled bodies stay in place
+        if !body1.enabled || matches!(body1.status, BodyStatus::Static) {
             continue;
         }
 
-        for coll1_handle in &body1.colliders {
-            let collider1 = colliders
-                .get(*coll1_handle)
-                .expect("Body cached nonexistent collider");
+        let move_x = sweep_x(*body1_handle, body1.movement.x, bodies, colliders, filter);
+        let body1 = bodies
+            .get_mut(*body1_handle)
+            .expect("Collider without a body");
+        body1.position.x += move_x;
+    }
+}
 
-            // for x step we skip sensors completely
-            if let ColliderState::Sensor = collider1.state {
+fn step_y<T, D>(
+    bodies: &mut BodySet<D>,
+    colliders: &ColliderSet<T>,
+    collision_graph: &mut CollisionGraph,
+    filter: Option<&dyn PairFilter<T, D>>,
+    body_handles: &[BodyHandle],
+) {
+    for body1_handle in body_handles {
+        let body1 = bodies.get(*body1_handle).expect("Collider without a body");
+
+        // disabled bodies stay in place
+        if !body1.enabled || matches!(body1.status, BodyStatus::Static) {
+            continue;
+        }
+
+        let move_y = sweep_y(
+            *body1_handle,
+            body1.movement.y,
+            bodies,
+            colliders,
+            collision_graph,
+            filter,
+        );
+        let body1 = bodies
+            .get_mut(*body1_handle)
+            .expect("Collider without a body");
+        body1.position.y += move_y;
+    }
+}
+
+/// Limits the movement of the body on x axis so it doesn't penetrate solid colliders.
+pub(crate) fn sweep_x<T, D>(
+    body1_handle: BodyHandle,
+    mut move_x: f32,
+    bodies: &BodySet<D>,
+    colliders: &ColliderSet<T>,
+    filter: Option<&dyn PairFilter<T, D>>,
+) -> f32 {
+    let body1 = bodies.get(body1_handle).expect("Collider without a body");
+    let positive = move_x > 0.;
+
+    for coll1_handle in &body1.colliders {
+        let collider1 = colliders
+            .get(*coll1_handle)
+            .expect("Body cached nonexistent collider");
+
+        // for x step we skip sensors completely
+        if let ColliderState::Sensor = collider1.state {
+            continue;
+        }
+
+        // TODO: Broadphase scan just the neighbours
+        for (coll2_handle, collider2) in colliders.iter() {
+            // no collider colliding with itself
+            if *coll1_handle == coll2_handle {
                 continue;
             }
 
-            // TODO: Broadphase scan just the neighbours
-            for (coll2_handle, collider2) in colliders.iter() {
-                // no collider colliding with itself
-                if *coll1_handle == coll2_handle {
-                    continue;
-                }
-
-                // for x step we skip sensors completely
-                if let ColliderState::Sensor = collider2.state {
-                    continue;
-                }
+            // for x step we skip sensors completely
+            if let ColliderState::Sensor = collider2.state {
+                continue;
+            }
 
-                if !can_collide(body1, collider1, collider2) {
-                    continue;
-                }
+            let body2 = bodies
+                .get(collider2.owner)
+                .expect("Collider without a body");
 
-                let body2 = bodies
-                    .get(collider2.owner)
-                    .expect("Collider without a body");
+            if pair_mode(body1, collider1, body2, collider2, filter) != PairMode::Block {
+                continue;
+            }
 
-                if is_penetrating(
+            // bodies already stuck inside are left to depenetration, they just can't sink deeper
+            if is_penetrating(collider1, body1.position, collider2, body2.position, 0.001) {
+                let movement = Vec2::new(move_x, 0.);
+                if sinks_deeper(
                     collider1,
-                    body1.position + Vec2::new(move_x, 0.),
+                    body1.position,
                     collider2,
                     body2.position,
-                    0.001,
+                    movement,
                 ) {
-                    if body1.velocity.x > 0. {
-                        move_x = move_x.min(
-                            body2.position.x - collider1.offset.x + collider2.offset.x
-                                - collider2.shape.half_exts.x
-                                - collider1.shape.half_exts.x
-                                - body1.position.x,
-                        );
-                    } else {
-                        move_x = move_x.max(
-                            body2.position.x - collider1.offset.x
-                                + collider2.offset.x
-                                + collider2.shape.half_exts.x
-                                + collider1.shape.half_exts.x
-                                - body1.position.x,
-                        );
-                    }
+                    move_x = 0.;
+                }
+                continue;
+            }
+
+            if is_penetrating(
+                collider1,
+                body1.position + Vec2::new(move_x, 0.),
+                collider2,
+                body2.position,
+                0.001,
+            ) {
+                if positive {
+                    move_x = move_x.min(
+                        body2.position.x - collider1.offset.x + collider2.offset.x
+                            - collider2.shape.half_exts.x
+                            - collider1.shape.half_exts.x
+                            - body1.position.x,
+                    );
+                } else {
+                    move_x = move_x.max(
+                        body2.position.x - collider1.offset.x
+                            + collider2.offset.x
+                            + collider2.shape.half_exts.x
+                            + collider1.shape.half_exts.x
+                            - body1.position.x,
+                    );
                 }
             }
         }
-        let body1 = bodies
-            .get_mut(*body1_handle)
-            .expect("Collider without a body");
-        body1.position.x += move_x;
     }
+    move_x
 }
 
-fn step_y<T>(
-    bodies: &mut BodySet,
-    colliders: &mut ColliderSet<T>,
+/// Limits the movement of the body on y axis so it doesn't penetrate solid colliders.  
+/// Colliders touching the body before the movement are registered in the `CollisionGraph`.
+pub(crate) fn sweep_y<T, D>(
+    body1_handle: BodyHandle,
+    mut move_y: f32,
+    bodies: &BodySet<D>,
+    colliders: &ColliderSet<T>,
     collision_graph: &mut CollisionGraph,
-    body_handles: &[BodyHandle],
-) {
-    for body1_handle in body_handles {
-        let body1 = bodies.get(*body1_handle).expect("Collider without a body");
-        let mut move_y = body1.movement.y;
+    filter: Option<&dyn PairFilter<T, D>>,
+) -> f32 {
+    let body1 = bodies.get(body1_handle).expect("Collider without a body");
+    let positive = move_y > 0.;
 
-        if let BodyStatus::Static = body1.status {
-            continue;
-        }
+    for coll1_handle in body1.colliders.iter() {
+        let collider1 = colliders
+            .get(*coll1_handle)
+            .expect("Body cached nonexistent collider");
 
-        for coll1_handle in body1.colliders.iter() {
-            let collider1 = colliders
-                .get(*coll1_handle)
-                .expect("Body cached nonexistent collider");
-
-            // TODO: Broadphase scan just the neighbours
-            for (coll2_handle, collider2) in colliders.iter() {
-                let coll2_handle = coll2_handle;
-                // no collider colliding with itself
-                if *coll1_handle == coll2_handle {
-                    continue;
-                }
+        // TODO: Broadphase scan just the neighbours
+        for (coll2_handle, collider2) in colliders.iter() {
+            let coll2_handle = coll2_handle;
+            // no collider colliding with itself
+            if *coll1_handle == coll2_handle {
+                continue;
+            }
 
-                if !can_collide(body1, collider1, collider2) {
-                    continue;
-                }
+            let body2 = bodies
+                .get(collider2.owner)
+                .expect("Collider without a body");
 
-                let body2 = bodies
-                    .get(collider2.owner)
-                    .expect("Collider without a body");
+            let mode = pair_mode(body1, collider1, body2, collider2, filter);
+            if mode == PairMode::Ignore {
+                continue;
+            }
 
-                if let (ColliderState::Solid, ColliderState::Solid) =
-                    (collider1.state, collider2.state)
+            if let (ColliderState::Solid, ColliderState::Solid, PairMode::Block) =
+                (collider1.state, collider2.state, mode)
+            {
+                // bodies already stuck inside are left to depenetration, they just can't sink deeper
+                let stuck =
+                    is_penetrating(collider1, body1.position, collider2, body2.position, 0.001);
+                let movement = Vec2::new(0., move_y);
+                if stuck
+                    && sinks_deeper(
+                        collider1,
+                        body1.position,
+                        collider2,
+                        body2.position,
+                        movement,
+                    )
                 {
-                    if is_penetrating(
+                    move_y = 0.;
+                } else if !stuck
+                    && is_penetrating(
                         collider1,
                         body1.position + Vec2::new(0., move_y),
                         collider2,
                         body2.position,
                         0.001,
-                    ) {
-                        if body1.velocity.y > 0. {
-                            move_y = move_y.min(
-                                body2.position.y - collider1.offset.y + collider2.offset.y
-                                    - collider2.shape.half_exts.y
-                                    - collider1.shape.half_exts.y
-                                    - body1.position.y,
-                            );
-                        } else {
-                            move_y = move_y.max(
-                                body2.position.y - collider1.offset.y
-                                    + collider2.offset.y
-                                    + collider2.shape.half_exts.y
-                                    + collider1.shape.half_exts.y
-                                    - body1.position.y,
-                            );
-                        }
+                    )
+                {
+                    if positive {
+                        move_y = move_y.min(
+                            body2.position.y - collider1.offset.y + collider2.offset.y
+                                - collider2.shape.half_exts.y
+                                - collider1.shape.half_exts.y
+                                - body1.position.y,
+                        );
+                    } else {
+                        move_y = move_y.max(
+                            body2.position.y - collider1.offset.y
+                                + collider2.offset.y
+                                + collider2.shape.half_exts.y
+                                + collider1.shape.half_exts.y
+                                - body1.position.y,
+                        );
                     }
                 }
-                if is_colliding(collider1, body1.position, collider2, body2.position) {
-                    collision_graph.update_edge(*coll1_handle, coll2_handle);
-                }
+            }
+            if is_colliding(collider1, body1.position, collider2, body2.position) {
+                collision_graph.update_edge(*coll1_handle, coll2_handle);
+            }
+        }
+    }
+    move_y
+}
+
+// Whether the movement points into the contact of the penetrating colliders.
+fn sinks_deeper<T>(
+    collider1: &Collider<T>,
+    position1: Vec2,
+    collider2: &Collider<T>,
+    position2: Vec2,
+    movement: Vec2,
+) -> bool {
+    collision_manifold(collider1, position1, collider2, position2)
+        .is_some_and(|manifold| manifold.best_contact().normal.dot(movement) > 0.)
+}
+
+/// Moves the body down by at most its `snap_distance` if there's ground within the reach.  
+/// Returns the distance moved.
+pub(crate) fn snap_to_ground<T, D>(
+    handle: BodyHandle,
+    up: Vec2,
+    bodies: &mut BodySet<D>,
+    colliders: &ColliderSet<T>,
+    collision_graph: &mut CollisionGraph,
+    filter: Option<&dyn PairFilter<T, D>>,
+) -> f32 {
+    let snap_y = -up.y.signum() * bodies[handle].snap_distance;
+    let move_y = sweep_y(handle, snap_y, bodies, colliders, collision_graph, filter);
+    // movement wasn't limited by anything so there's no ground to snap to
+    if move_y.abs() >= snap_y.abs() {
+        return 0.;
+    }
+    bodies[handle].position.y += move_y;
+    register_edges(handle, bodies, colliders, collision_graph, filter);
+    move_y
+}
+
+/// Pushes the body out of the solid colliders of other bodies it penetrates, by at most `max_distance` in total.  
+/// Returns the distance moved.
+pub(crate) fn resolve_penetration<T, D>(
+    handle: BodyHandle,
+    max_distance: f32,
+    bodies: &mut BodySet<D>,
+    colliders: &ColliderSet<T>,
+    filter: Option<&dyn PairFilter<T, D>>,
+) -> Vec2 {
+    use ColliderState::Solid;
+    let start = bodies[handle].position;
+    // pushing the body out of one collider can push it into another
+    for _ in 0..4 {
+        let bodies_ref = &*bodies;
+        let body = &bodies_ref[handle];
+        let deepest = body
+            .colliders
+            .iter()
+            .map(|h| &colliders[*h])
+            .filter(|collider1| matches!(collider1.state, Solid))
+            .flat_map(|collider1| {
+                colliders.iter().filter_map(move |(_, collider2)| {
+                    if collider2.owner == handle || !matches!(collider2.state, Solid) {
+                        return None;
+                    }
+                    let body2 = &bodies_ref[collider2.owner];
+                    if pair_mode(body, collider1, body2, collider2, filter) != PairMode::Block {
+                        return None;
+                    }
+                    let manifold =
+                        collision_manifold(collider1, body.position, collider2, body2.position)?;
+                    let contact = manifold.best_contact();
+                    Some(contact.normal * contact.depth)
+                })
+            })
+            .filter(|push| push.length() > 0.001)
+            .max_by(|a, b| {
+                a.length()
+                    .partial_cmp(&b.length())
+                    .unwrap_or(std::cmp::Ordering::Less)
+            });
+        let push = match deepest {
+            Some(push) => push,
+            None => break,
+        };
+        let correction = (body.position - push - start).clamp_length_max(max_distance);
+        bodies[handle].position = start + correction;
+        if correction.length() >= max_distance {
+            break;
+        }
+    }
+    bodies[handle].position - start
+}
+
+// Finds a solid collider the resized collider would get stuck in.
+// Colliders it was already stuck in before are left to depenetration.
+fn obstruction<T, D>(
+    handle: ColliderHandle,
+    previous: &Collider<T>,
+    bodies: &BodySet<D>,
+    colliders: &ColliderSet<T>,
+    filter: Option<&dyn PairFilter<T, D>>,
+) -> Option<ColliderHandle> {
+    use ColliderState::Solid;
+    let collider1 = &colliders[handle];
+    if !matches!(collider1.state, Solid) {
+        return None;
+    }
+    let body1 = &bodies[collider1.owner];
+    colliders
+        .iter()
+        .filter(|(h, collider2)| *h != handle && matches!(collider2.state, Solid))
+        .find(|(_, collider2)| {
+            let body2 = &bodies[collider2.owner];
+            pair_mode(body1, collider1, body2, collider2, filter) == PairMode::Block
+                && is_penetrating(collider1, body1.position, collider2, body2.position, 0.001)
+                && !is_penetrating(previous, body1.position, collider2, body2.position, 0.001)
+        })
+        .map(|(h, _)| h)
+}
+
+/// Whether any of body's colliders collides with a collider of another body below it.
+pub(crate) fn is_grounded<T, D>(
+    handle: BodyHandle,
+    up: Vec2,
+    bodies: &BodySet<D>,
+    colliders: &ColliderSet<T>,
+    collision_graph: &CollisionGraph,
+) -> bool {
+    bodies[handle].colliders.iter().any(|collider_handle| {
+        collision_graph
+            .collisions_from(*collider_handle)
+            .any(|(other, info)| colliders[other].owner != handle && info.normal.dot(up) < -0.5)
+    })
+}
+
+// Adds edges between body's colliders and everything they currently touch.
+fn register_edges<T, D>(
+    handle: BodyHandle,
+    bodies: &BodySet<D>,
+    colliders: &ColliderSet<T>,
+    collision_graph: &mut CollisionGraph,
+    filter: Option<&dyn PairFilter<T, D>>,
+) {
+    let body = &bodies[handle];
+    for coll1_handle in body.colliders.iter() {
+        let collider1 = &colliders[*coll1_handle];
+        for (coll2_handle, collider2) in colliders.iter() {
+            if *coll1_handle == coll2_handle {
+                continue;
+            }
+            let body2 = bodies
+                .get(collider2.owner)
+                .expect("Collider without a body");
+            if pair_mode(body, collider1, body2, collider2, filter) == PairMode::Ignore {
+                continue;
+            }
+            if is_colliding(collider1, body.position, collider2, body2.position) {
+                collision_graph.update_edge(*coll1_handle, coll2_handle);
             }
         }
-        let body1 = bodies
-            .get_mut(*body1_handle)
-            .expect("Collider without a body");
-        body1.position.y += move_y;
     }
 }
 
-fn can_collide<T>(body1: &Body, collider1: &Collider<T>, collider2: &Collider<T>) -> bool {
+// Decides how the colliders interact, the user supplied filter has the final say.
+fn pair_mode<T, D>(
+    body1: &Body<D>,
+    collider1: &Collider<T>,
+    body2: &Body<D>,
+    collider2: &Collider<T>,
+    filter: Option<&dyn PairFilter<T, D>>,
+) -> PairMode {
+    // disabled bodies and colliders don't interact with anything
+    if !(body1.enabled && collider1.enabled && body2.enabled && collider2.enabled) {
+        return PairMode::Ignore;
+    }
     let category_mismatch = ((collider1.category_bits & collider2.mask_bits) == 0)
         || ((collider2.category_bits & collider1.mask_bits) == 0);
     // only colliders with matching masks can collide
     if category_mismatch {
-        return false;
+        return PairMode::Ignore;
+    }
+
+    // excluded with `ignore_pair`
+    if body1.ignored.contains(&collider2.owner) {
+        return PairMode::Ignore;
     }
 
     // don't collide with same body if it's disabled
     if collider1.owner == collider2.owner && !body1.self_collide {
-        return false;
+        return PairMode::Ignore;
+    }
+
+    let mode = match filter {
+        Some(filter) => filter.filter(collider1, body1, collider2, body2),
+        None => PairMode::Block,
+    };
+    // sensing is symmetric, either collider can ask for it
+    let sensing = ((collider1.category_bits & collider2.sense_bits) != 0)
+        || ((collider2.category_bits & collider1.sense_bits) != 0);
+    match mode {
+        PairMode::Block if sensing => PairMode::Overlap,
+        _ => mode,
+    }
+}
+
+// Applies the collisions adjusted by the user to the velocities of kinematic bodies.
+fn respond_to_contacts<T, D>(
+    modifier: &dyn ContactModifier<T, D>,
+    bodies: &mut BodySet<D>,
+    colliders: &ColliderSet<T>,
+    collision_graph: &CollisionGraph,
+    body_handles: &[BodyHandle],
+) {
+    for body1_handle in body_handles {
+        let body1 = &bodies[*body1_handle];
+        if let BodyStatus::Static = body1.status {
+            continue;
+        }
+
+        // contacts sharing the normal, e.g. with a row of tiles, act as a single surface
+        let mut surfaces: Vec<(ModifiableContact, f32)> = Vec::new();
+        for coll1_handle in body1.colliders.iter() {
+            let collider1 = &colliders[*coll1_handle];
+            for (coll2_handle, info) in collision_graph.collisions_from(*coll1_handle) {
+                let collider2 = &colliders[coll2_handle];
+                if collider2.owner == *body1_handle {
+                    continue;
+                }
+                let body2 = &bodies[collider2.owner];
+
+                let mut contact = ModifiableContact::new(info.normal);
+                modifier.modify(collider1, body1, collider2, body2, &mut contact);
+                let surface = surfaces
+                    .iter_mut()
+                    .find(|(surface, _)| surface.normal.dot(contact.normal) > 0.999);
+                match surface {
+                    Some((surface, count)) => {
+                        surface.surface_velocity += contact.surface_velocity;
+                        surface.friction += contact.friction;
+                        surface.restitution = match (surface.restitution, contact.restitution) {
+                            (Some(r1), Some(r2)) => Some(r1.max(r2)),
+                            (r1, r2) => r1.or(r2),
+                        };
+                        *count += 1.;
+                    }
+                    None => surfaces.push((contact, 1.)),
+                }
+            }
+        }
+
+        let mut velocity = body1.velocity;
+        let mut surface_velocity = Vec2::ZERO;
+        for (mut surface, count) in surfaces {
+            surface.surface_velocity /= count;
+            surface.friction /= count;
+            velocity = surface.apply(velocity);
+            surface_velocity += surface.surface_velocity;
+        }
+
+        let body1 = &mut bodies[*body1_handle];
+        body1.velocity = velocity;
+        body1.surface_velocity = surface_velocity;
     }
-    true
 }
 
-fn describe_collisions<T: Copy>(
-    bodies: &BodySet,
+fn describe_collisions<T: Clone, D>(
+    bodies: &BodySet<D>,
     colliders: &ColliderSet<T>,
     collision_graph: &mut CollisionGraph,
+    filter: Option<&dyn PairFilter<T, D>>,
     events: &mut Vec<ContactEvent<T>>,
 ) {
     // TODO: Don't reallocate
@@ -376,50 +1225,123 @@ fn describe_collisions<T: Copy>(
 
     // collision event and contact information
     for edge_id in collision_graph.src.edge_indices() {
-        let (node1_id, node2_id) = collision_graph.src.edge_endpoints(edge_id).unwrap();
-        let handle1 = collision_graph.src[node1_id];
-        let handle2 = collision_graph.src[node2_id];
-        let collider1 = &colliders[handle1];
-        let collider2 = &colliders[handle2];
-
-        let previous_interaction = collision_graph.src.edge_weight_mut(edge_id).unwrap();
-
-        let position1 = bodies
-            .get(collider1.owner)
-            .expect("Collider without a body")
-            .position;
-        let position2 = bodies
-            .get(collider2.owner)
-            .expect("Collider without a body")
-            .position;
-
-        let current_interaction = {
-            use ColliderState::Solid;
-            if let (Solid, Solid) = (collider1.state, collider2.state) {
-                collision_manifold(collider1, position1, collider2, position2).map(|manifold| {
-                    Interaction::Collision(CollisionInfo::from(manifold.best_contact()))
-                })
-            } else if is_colliding(collider1, position1, collider2, position2) {
-                Some(Interaction::Overlap)
-            } else {
-                None
-            }
-        };
+        if !update_interaction(
+            edge_id,
+            bodies,
+            colliders,
+            collision_graph,
+            filter,
+            events,
+            true,
+        ) {
+            removed_edges.push(collision_graph.src.edge_endpoints(edge_id).unwrap());
+        }
+    }
+
+    remove_edges(collision_graph, removed_edges);
+}
+
+// Replaces the interaction stored in the edge with the current one, generating Started/Ended events.
+// Ongoing events are generated only if `report_ongoing` is set, as they're meant to be sent once per step.
+// Returns whether the edge is still needed.
+fn update_interaction<T: Clone, D>(
+    edge_id: EdgeIndex<usize>,
+    bodies: &BodySet<D>,
+    colliders: &ColliderSet<T>,
+    collision_graph: &mut CollisionGraph,
+    filter: Option<&dyn PairFilter<T, D>>,
+    events: &mut Vec<ContactEvent<T>>,
+    report_ongoing: bool,
+) -> bool {
+    let (node1_id, node2_id) = collision_graph.src.edge_endpoints(edge_id).unwrap();
+    let handle1 = collision_graph.src[node1_id];
+    let handle2 = collision_graph.src[node2_id];
+    let collider1 = &colliders[handle1];
+    let collider2 = &colliders[handle2];
+
+    let previous_interaction = collision_graph.src.edge_weight_mut(edge_id).unwrap();
+
+    let body1 = bodies
+        .get(collider1.owner)
+        .expect("Collider without a body");
+    let body2 = bodies
+        .get(collider2.owner)
+        .expect("Collider without a body");
+    let (position1, position2) = (body1.position, body2.position);
 
-        if current_interaction.is_some() && previous_interaction.is_none() {
-            events.push(ContactEvent::new(handle1, collider1, handle2, collider2));
+    let mode = pair_mode(body1, collider1, body2, collider2, filter);
+
+    let mut contact = ContactData::default();
+    let current_interaction = {
+        use ColliderState::Solid;
+        if mode == PairMode::Ignore {
+            None
+        } else if let (Solid, Solid, PairMode::Block) = (collider1.state, collider2.state, mode) {
+            collision_manifold(collider1, position1, collider2, position2).map(|manifold| {
+                let best_contact = manifold.best_contact();
+                contact = ContactData {
+                    normal: best_contact.normal,
+                    depth: best_contact.depth,
+                    contact_point: best_contact.contact_point,
+                    relative_velocity: body2.driven_velocity - body1.driven_velocity,
+                };
+                Interaction::Collision(CollisionInfo::from(best_contact))
+            })
+        } else if is_colliding(collider1, position1, collider2, position2) {
+            Some(Interaction::Overlap)
+        } else {
+            None
         }
-        if current_interaction.is_none() {
-            removed_edges.push((node1_id, node2_id));
-            if previous_interaction.is_some() {
-                events.push(
-                    ContactEvent::new(handle1, collider1, handle2, collider2).into_finished(),
-                );
-            }
+    };
+
+    // colliders with disabled events don't report the interaction at all
+    let event_of = |interaction| {
+        emits_events(collider1, collider2, interaction).then(|| {
+            ContactEvent::for_interaction(handle1, collider1, handle2, collider2, interaction)
+        })
+    };
+    match (&previous_interaction, &current_interaction) {
+        (None, Some(current)) => {
+            events.extend(event_of(current).map(|e| e.with_contact(handle1, contact)))
+        }
+        // the kind of interaction changed, e.g. because of the filter
+        (Some(previous), Some(current)) if previous.is_overlap() != current.is_overlap() => {
+            events.extend(event_of(previous).map(ContactEvent::into_finished));
+            events.extend(event_of(current).map(|e| e.with_contact(handle1, contact)));
+        }
+        (Some(_), Some(current))
+            if report_ongoing && (collider1.ongoing_events || collider2.ongoing_events) =>
+        {
+            events.extend(event_of(current).map(ContactEvent::into_ongoing))
         }
-        *previous_interaction = current_interaction;
+        (Some(previous), None) => {
+            events.extend(event_of(previous).map(ContactEvent::into_finished))
+        }
+        _ => (),
+    }
+    let keep = current_interaction.is_some();
+    *previous_interaction = current_interaction;
+    keep
+}
+
+// Whether both colliders want to send events about the interaction.
+fn emits_events<T>(
+    collider1: &Collider<T>,
+    collider2: &Collider<T>,
+    interaction: &Interaction,
+) -> bool {
+    match interaction {
+        Interaction::Collision(_) => {
+            collider1.emit_collision_events && collider2.emit_collision_events
+        }
+        Interaction::Overlap => collider1.emit_overlap_events && collider2.emit_overlap_events,
     }
+}
 
+fn remove_edges(
+    collision_graph: &mut CollisionGraph,
+    removed_edges: Vec<(NodeIndex<usize>, NodeIndex<usize>)>,
+) {
     removed_edges.into_iter().for_each(|(node1_id, node2_id)| {
         if let Some(edge_id) = collision_graph.src.find_edge(node1_id, node2_id) {
             if collision_graph.src.remove_edge(edge_id).is_none() {
@@ -434,3 +1356,513 @@ fn describe_collisions<T: Copy>(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::{BodyDesc, ColliderDesc};
+    use crate::testing::{
+        aabb, add_box, add_static, step_events, world_with_floor, world_with_tiles, DT,
+    };
+    use crate::{
+        Body, BodyEvent, CharacterController, Collider, ColliderHandle, ColliderState,
+        CollisionLayers, ContactData, ContactEvent, Error, ModifiableContact, Ray, World,
+    };
+    use glam::Vec2;
+
+    // contact data of the collision involving the collider that started during the step
+    fn started_contact(events: &[ContactEvent<()>], collider: ColliderHandle) -> ContactData {
+        events
+            .iter()
+            .find_map(|event| match event {
+                ContactEvent::CollisionStarted(h1, h2, _, _, contact)
+                    if *h1 == collider || *h2 == collider =>
+                {
+                    Some(*contact)
+                }
+                _ => None,
+            })
+            .expect("no collision started")
+    }
+
+    #[test]
+    fn conveyor_seam_isnt_counted_twice() {
+        let mut world = world_with_tiles(&[-8., 8.]);
+        // standing on the seam between both tiles
+        let (body, collider) = add_box(&mut world, Vec2::new(0., -16.), Vec2::new(0., 60.));
+        world.physics.set_contact_modifier(
+            |_: &Collider<()>,
+             _: &Body,
+             _: &Collider<()>,
+             _: &Body,
+             contact: &mut ModifiableContact| {
+                contact.surface_velocity = Vec2::new(60., 0.);
+                contact.friction = 0.5;
+            },
+        );
+
+        for _ in 0..3 {
+            world.step(DT);
+        }
+        assert_eq!(world.physics.collisions_of(collider).count(), 2);
+        let start = world.body(body).unwrap().position;
+        world.step(DT);
+        let moved = world.body(body).unwrap().position - start;
+        assert!((moved.x - 1.).abs() < 1e-4, "moved by {}", moved.x);
+        assert_eq!(moved.y, 0.);
+    }
+
+    #[test]
+    fn partial_depenetration_under_gravity() {
+        let mut world = world_with_floor();
+        world.physics.max_depenetration = 1.;
+        // 3 units deep in the floor, falling 2 units per step
+        let (body, _) = add_box(&mut world, Vec2::new(0., -13.), Vec2::new(0., 120.));
+
+        let mut previous_y = -13.;
+        for _ in 0..10 {
+            world.step(DT);
+            let y = world.body(body).unwrap().position.y;
+            assert!(y <= previous_y, "sank from {} to {}", previous_y, y);
+            assert!(y >= previous_y - 1., "pushed by more than the limit");
+            previous_y = y;
+        }
+        assert_eq!(previous_y, -16.);
+    }
+
+    #[test]
+    fn stuck_body_doesnt_sink_without_depenetration() {
+        let mut world = world_with_floor();
+        world.physics.max_depenetration = 0.;
+        let (body, _) = add_box(&mut world, Vec2::new(0., -13.), Vec2::new(60., 120.));
+
+        for _ in 0..10 {
+            world.step(DT);
+        }
+        // still stuck, but free to move sideways
+        assert_eq!(world.body(body).unwrap().position, Vec2::new(10., -13.));
+    }
+
+    #[test]
+    fn depenetration_is_limited_per_step() {
+        let mut world = world_with_floor();
+        add_static(&mut world, Vec2::new(40., -40.), Vec2::new(8., 32.));
+        // 3 units deep into the wall's left side
+        let (body, _) = add_box(&mut world, Vec2::new(27., -16.), Vec2::ZERO);
+        world.physics.max_depenetration = 1.;
+
+        for expected in [26., 25., 24., 24.] {
+            world.step(DT);
+            assert_eq!(
+                world.body(body).unwrap().position,
+                Vec2::new(expected, -16.)
+            );
+        }
+    }
+
+    #[test]
+    fn sliding_across_tile_seam_isnt_blocked() {
+        let mut world = world_with_tiles(&[-8., 8.]);
+        let (body, _) = add_box(&mut world, Vec2::new(-8., -16.), Vec2::new(60., 60.));
+
+        for step in 1..=16 {
+            world.step(DT);
+            let position = world.body(body).unwrap().position;
+            assert_eq!(position, Vec2::new(-8. + step as f32, -16.));
+        }
+    }
+
+    #[test]
+    fn excluding_queries_skip_the_body_and_ignored_bodies() {
+        let mut world: World<()> = World::new();
+        let (body, _) = add_box(&mut world, Vec2::new(0., 0.), Vec2::ZERO);
+        let (ignored, _) = add_box(&mut world, Vec2::new(16., 0.), Vec2::ZERO);
+        let (_, other) = add_box(&mut world, Vec2::new(32., 0.), Vec2::ZERO);
+        world.ignore_pair(body, ignored).unwrap();
+
+        let overlapping: Vec<_> = world
+            .overlap_test_excluding(body, Vec2::new(16., 0.), Vec2::new(24., 4.), u32::MAX)
+            .collect();
+        assert_eq!(overlapping, vec![other]);
+        assert_eq!(
+            world
+                .overlap_test(Vec2::new(16., 0.), Vec2::new(24., 4.), u32::MAX)
+                .count(),
+            3
+        );
+
+        let ray = Ray {
+            origin: Vec2::new(0., 0.),
+            dir: Vec2::new(1., 0.),
+            toi: 64.,
+        };
+        let hits: Vec<_> = world
+            .project_ray_excluding(body, &ray, u32::MAX)
+            .map(|(h, _)| h)
+            .collect();
+        assert_eq!(hits, vec![other]);
+    }
+
+    #[test]
+    fn body_events_carry_current_user_data() {
+        let mut world: World<(), &str> = World::new();
+        world.physics.report_body_contacts = true;
+        let (floor, _) = add_static(&mut world, Vec2::ZERO, Vec2::new(80., 8.));
+        let (player, _) = add_box(&mut world, Vec2::new(0., -17.), Vec2::new(0., 60.));
+        world.body_mut(floor).unwrap().user_data = "floor";
+        world.body_mut(player).unwrap().user_data = "player";
+        // the smaller handle comes first
+        let ordered = |floor_data, player_data| {
+            if floor < player {
+                (floor, player, floor_data, player_data)
+            } else {
+                (player, floor, player_data, floor_data)
+            }
+        };
+
+        let mut started = Vec::new();
+        for _ in 0..3 {
+            world.step(DT);
+            started.extend(world.body_events().iter().cloned());
+        }
+        let (h1, h2, d1, d2) = ordered("floor", "player");
+        assert_eq!(started, vec![BodyEvent::BodyContactStarted(h1, h2, d1, d2)]);
+
+        // data changed during the contact, the floor's data is kept after its removal
+        world.body_mut(player).unwrap().user_data = "renamed";
+        world.remove_body(floor);
+        world.step(DT);
+        let (h1, h2, d1, d2) = ordered("floor", "renamed");
+        assert_eq!(
+            world.body_events(),
+            &vec![BodyEvent::BodyContactEnded(h1, h2, d1, d2)]
+        );
+    }
+
+    #[test]
+    fn advance_keeps_the_leftover_time() {
+        let mut world = world_with_floor();
+        assert_eq!(world.advance(2.5 * DT), 2);
+        assert!((world.alpha() - 0.5).abs() < 1e-3);
+        assert_eq!(world.advance(0.75 * DT), 1);
+        assert!((world.alpha() - 0.25).abs() < 1e-3);
+    }
+
+    #[test]
+    fn advance_drops_time_beyond_max_steps() {
+        let mut world = world_with_floor();
+        world.physics.max_steps = 3;
+        assert_eq!(world.advance(10.25 * DT), 3);
+        assert!((world.alpha() - 0.25).abs() < 1e-3);
+    }
+
+    #[test]
+    fn interpolated_position_blends_the_last_step() {
+        let mut world = world_with_floor();
+        let (body, _) = add_box(&mut world, Vec2::new(0., -100.), Vec2::new(60., 0.));
+        world.advance(1.5 * DT);
+
+        let body = world.body(body).unwrap();
+        assert_eq!(body.position, Vec2::new(1., -100.));
+        let interpolated = body.interpolated_position(world.alpha());
+        assert!((interpolated - Vec2::new(0.5, -100.)).length() < 1e-3);
+    }
+
+    #[test]
+    #[should_panic(expected = "timestep has to be positive")]
+    fn advance_rejects_zero_timestep() {
+        let mut world = world_with_floor();
+        world.physics.timestep = 0.;
+        assert_eq!(world.alpha(), 0.);
+        world.advance(DT);
+    }
+
+    #[test]
+    fn impact_reports_velocity_of_the_falling_body() {
+        let mut world = world_with_floor();
+        let (_, collider) = add_box(&mut world, Vec2::new(0., -17.), Vec2::new(0., 120.));
+
+        let mut events = Vec::new();
+        for _ in 0..3 {
+            events.extend(step_events(&mut world));
+        }
+        let contact = started_contact(&events, collider);
+        assert_eq!(contact.relative_velocity.abs(), Vec2::new(0., 120.));
+    }
+
+    #[test]
+    fn impact_reports_velocity_of_move_and_slide() {
+        let mut world = world_with_floor();
+        let (_, wall) = add_static(&mut world, Vec2::new(24., -40.), Vec2::new(8., 32.));
+        let (body, _) = add_box(&mut world, Vec2::new(0., -20.), Vec2::ZERO);
+
+        world.move_and_slide(&CharacterController::new(), body, Vec2::new(12., 0.));
+        let contact = started_contact(&step_events(&mut world), wall);
+        assert!((contact.relative_velocity.abs() - Vec2::new(12. / DT, 0.)).length() < 1e-2);
+    }
+
+    #[test]
+    fn handler_receives_removal_events_once() {
+        use std::sync::{Arc, Mutex};
+        let mut world = world_with_floor();
+        let (body, _) = add_box(&mut world, Vec2::new(0., -16.), Vec2::new(0., 60.));
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let log = received.clone();
+        world
+            .physics
+            .set_event_handler(move |event: &ContactEvent<()>| log.lock().unwrap().push(*event));
+
+        world.step(DT);
+        let started = received.lock().unwrap().drain(..).collect::<Vec<_>>();
+        assert!(matches!(started[..], [ContactEvent::CollisionStarted(..)]));
+
+        world.remove_body(body);
+        let ended = received.lock().unwrap().drain(..).collect::<Vec<_>>();
+        assert!(matches!(ended[..], [ContactEvent::CollisionEnded(..)]));
+
+        // polling still sees the event with the next step, the handler isn't called again
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionEnded(..)]));
+        assert!(received.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn events_need_both_colliders_to_emit_them() {
+        let mut world = world_with_floor();
+        let (floor, _) = world.colliders.iter().next().unwrap();
+        world.collider_mut(floor).unwrap().emit_collision_events = false;
+        let (body, collider) = add_box(&mut world, Vec2::new(0., -16.), Vec2::new(0., 60.));
+        let (_, sensor) = add_static(&mut world, Vec2::new(0., -16.), Vec2::new(4., 4.));
+        world.collider_mut(sensor).unwrap().state = ColliderState::Sensor;
+        world.collider_mut(collider).unwrap().emit_overlap_events = false;
+
+        // only one side of each pair refuses, but that's enough
+        assert!(step_events(&mut world).is_empty());
+        assert_eq!(world.physics.collisions_of(collider).count(), 1);
+        assert_eq!(world.physics.overlaps_of(collider).count(), 1);
+
+        world.collider_mut(collider).unwrap().emit_overlap_events = true;
+        world.remove_body(body);
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::OverlapEnded(..)]));
+    }
+
+    #[test]
+    fn fallible_calls_report_stale_handles() {
+        let mut world = world_with_floor();
+        let (body, collider) = add_box(&mut world, Vec2::new(0., -16.), Vec2::ZERO);
+        world.remove_body(body);
+
+        assert_eq!(world.try_remove_body(body), Err(Error::StaleBody(body)));
+        assert_eq!(
+            world.try_remove_collider(collider),
+            Err(Error::StaleCollider(collider))
+        );
+        assert_eq!(
+            world.teleport(body, Vec2::ZERO, false),
+            Err(Error::StaleBody(body))
+        );
+        let orphan = ColliderDesc::new(aabb(8., 8.), ()).build(body);
+        let error = world.try_add_collider(orphan).unwrap_err();
+        assert_eq!(error, Error::MissingBody(body));
+        assert_eq!(error.to_string(), format!("owner {:?} doesn't exist", body));
+        assert_eq!(world.try_step(DT), Ok(()));
+    }
+
+    #[test]
+    fn desynced_collider_is_reported_instead_of_panicking() {
+        let mut world = world_with_floor();
+        let (body, collider) = add_box(&mut world, Vec2::new(0., -16.), Vec2::ZERO);
+        let (other, _) = add_box(&mut world, Vec2::new(0., -48.), Vec2::ZERO);
+        world.collider_mut(collider).unwrap().owner = other;
+
+        assert_eq!(world.try_step(DT), Err(Error::OwnerDesync(collider)));
+        assert_eq!(
+            world.try_remove_collider(collider),
+            Err(Error::OwnerDesync(collider))
+        );
+        assert!(world.collider(collider).is_some());
+
+        world.collider_mut(collider).unwrap().owner = body;
+        world.physics.collision_graph.remove_node(collider);
+        assert_eq!(world.try_step(DT), Err(Error::GraphDesync(collider)));
+        assert_eq!(
+            world.try_remove_collider(collider),
+            Err(Error::GraphDesync(collider))
+        );
+    }
+
+    #[test]
+    fn reparenting_moves_contacts_and_caches() {
+        let mut world = world_with_floor();
+        let (body, collider) = add_box(&mut world, Vec2::new(0., -16.), Vec2::new(0., 60.));
+        let (other, other_collider) = add_box(&mut world, Vec2::new(0., -100.), Vec2::ZERO);
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionStarted(..)]));
+
+        world
+            .reparent_collider(collider, other, Vec2::new(20., 0.))
+            .unwrap();
+        assert!(world.body(body).unwrap().colliders.is_empty());
+        assert_eq!(
+            world.body(other).unwrap().colliders,
+            vec![other_collider, collider]
+        );
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionEnded(..)]));
+
+        world.reparent_collider(collider, body, Vec2::ZERO).unwrap();
+        assert_eq!(world.body(body).unwrap().colliders, vec![collider]);
+        assert_eq!(world.body(other).unwrap().colliders, vec![other_collider]);
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionStarted(..)]));
+    }
+
+    #[test]
+    fn disabling_ends_interactions_until_reenabled() {
+        let mut world = world_with_floor();
+        let (body, collider) = add_box(&mut world, Vec2::new(0., -16.), Vec2::new(0., 60.));
+        step_events(&mut world);
+
+        world.body_mut(body).unwrap().enabled = false;
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionEnded(..)]));
+        // disabled bodies don't move and aren't found by the queries
+        assert_eq!(world.body(body).unwrap().position, Vec2::new(0., -16.));
+        assert_eq!(
+            world
+                .overlap_test(Vec2::new(0., -16.), Vec2::new(1., 1.), u32::MAX)
+                .count(),
+            0
+        );
+
+        world.body_mut(body).unwrap().enabled = true;
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionStarted(..)]));
+
+        world.collider_mut(collider).unwrap().enabled = false;
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionEnded(..)]));
+        world.collider_mut(collider).unwrap().enabled = true;
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionStarted(..)]));
+    }
+
+    #[test]
+    fn teleport_depenetrates_along_the_shallowest_axis() {
+        let mut world = world_with_floor();
+        let (body, _) = add_box(&mut world, Vec2::new(0., -100.), Vec2::ZERO);
+
+        world.teleport(body, Vec2::new(4., -14.), false).unwrap();
+        assert_eq!(world.body(body).unwrap().position, Vec2::new(4., -14.));
+        world.teleport(body, Vec2::new(4., -14.), true).unwrap();
+        assert_eq!(world.body(body).unwrap().position, Vec2::new(4., -16.));
+    }
+
+    #[test]
+    fn teleport_ends_and_starts_collisions() {
+        let mut world = world_with_floor();
+        let (body, collider) = add_box(&mut world, Vec2::new(0., -16.), Vec2::new(0., 60.));
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionStarted(..)]));
+
+        world.teleport(body, Vec2::new(0., -100.), false).unwrap();
+        world.body_mut(body).unwrap().velocity = Vec2::ZERO;
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionEnded(..)]));
+        assert!(events[0].involves(collider));
+
+        world.teleport(body, Vec2::new(0., -16.), false).unwrap();
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionStarted(..)]));
+        assert!(step_events(&mut world).is_empty());
+    }
+
+    #[test]
+    fn resize_into_sensor_overlaps_and_into_solid_fails() {
+        let mut world = world_with_floor();
+        let (_, sensor) = add_static(&mut world, Vec2::new(0., -40.), Vec2::new(8., 8.));
+        world.collider_mut(sensor).unwrap().state = ColliderState::Sensor;
+        let (_, collider) = add_box(&mut world, Vec2::new(0., -20.), Vec2::ZERO);
+        assert!(step_events(&mut world).is_empty());
+
+        // grows up into the sensor
+        world
+            .resize_collider(collider, aabb(8., 12.), Vec2::new(0., -4.))
+            .unwrap();
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::OverlapStarted(..)]));
+
+        // growing down would sink it into the floor
+        let error = world
+            .resize_collider(collider, aabb(8., 12.), Vec2::new(0., 4.))
+            .unwrap_err();
+        assert!(matches!(error, Error::Obstructed(_)));
+        assert_eq!(world.collider(collider).unwrap().offset, Vec2::new(0., -4.));
+        assert!(step_events(&mut world).is_empty());
+
+        world
+            .resize_collider(collider, aabb(8., 8.), Vec2::ZERO)
+            .unwrap();
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::OverlapEnded(..)]));
+    }
+
+    #[test]
+    fn layers_decide_between_blocking_overlapping_and_ignoring() {
+        let mut layers = CollisionLayers::new();
+        for name in ["ground", "player", "ghost"] {
+            layers.add_layer(name);
+        }
+        layers.set_interaction("ghost", "ground", false);
+        layers.set_sensing("player", "ghost", true);
+
+        let mut world: World<()> = World::new();
+        let ground = world.add_body(BodyDesc::new().make_static().build());
+        let falling = BodyDesc::new()
+            .with_position(Vec2::new(0., -16.))
+            .with_velocity(Vec2::new(0., 60.));
+        let player = world.add_body(falling.clone().build());
+        let ghost = world.add_body(falling.build());
+        let mut colliders = Vec::new();
+        for (body, shape, layer) in [
+            (ground, aabb(80., 8.), "ground"),
+            (player, aabb(8., 8.), "player"),
+            (ghost, aabb(8., 8.), "ghost"),
+        ] {
+            let desc = ColliderDesc::new(shape, ()).with_layer(&layers, layer);
+            colliders.push(world.add_collider(desc.build(body)).unwrap());
+        }
+
+        let events = step_events(&mut world);
+        assert_eq!(events.len(), 2);
+        // the player rests on the ground and only overlaps the ghost
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, ContactEvent::CollisionStarted(..))));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, ContactEvent::OverlapStarted(..))));
+        assert!(events.iter().all(|event| event.involves(colliders[1])));
+        assert_eq!(world.body(ghost).unwrap().position.y, -15.);
+    }
+
+    #[test]
+    fn ignored_pair_ends_and_restarts_collision() {
+        let mut world: World<()> = World::new();
+        let (floor, _) = add_static(&mut world, Vec2::ZERO, Vec2::new(80., 8.));
+        let (body, _) = add_box(&mut world, Vec2::new(0., -16.), Vec2::new(0., 60.));
+        step_events(&mut world);
+
+        world.ignore_pair(body, floor).unwrap();
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionEnded(..)]));
+        // falls through the floor while ignored
+        assert!(world.body(body).unwrap().position.y > -16.);
+
+        world.teleport(body, Vec2::new(0., -16.), false).unwrap();
+        world.unignore_pair(body, floor).unwrap();
+        let events = step_events(&mut world);
+        assert!(matches!(events[..], [ContactEvent::CollisionStarted(..)]));
+        assert_eq!(world.body(body).unwrap().position.y, -16.);
+    }
+}